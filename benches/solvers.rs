@@ -0,0 +1,74 @@
+//! Times the generator and the solver of each part separately, on the inputs in input/<year>.
+//! Benchmarks are grouped by year and day and named `YYYY/dayN/generator/partM` and `YYYY/dayN/solver/partM`.
+//!
+//! ```text
+//! cargo bench --bench solvers -- 2020/day17/              # only day 17 of 2020
+//! cargo bench --bench solvers -- --save-baseline before   # record a baseline
+//! cargo bench --bench solvers -- --baseline before        # compare with it
+//! ```
+//!
+//! Days without an input file are left out.
+
+extern crate aoc;
+extern crate criterion;
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, SamplingMode};
+
+use aoc::registry;
+
+
+/// Parts taking longer than this for a single run are sampled less often.
+const SLOW: Duration = Duration::from_millis(50);
+/// Sample size of slow parts, and criterion's default for the others.
+const SLOW_SAMPLES: usize = 10;
+const SAMPLES: usize = 100;
+
+
+fn bench_day(c: &mut Criterion, year: u32, day: u32, input: &str) {
+    let mut group = c.benchmark_group(format!("{}/day{}", year, day));
+    for solver in registry::for_day(year, day) {
+        let part = format!("part{}", solver.part);
+        let outcome = match solver.run(input) {
+            Ok(o) => o,
+            Err(e) => {
+                eprintln!("day {} {}: {}", day, part, e);
+                continue;
+            },
+        };
+        // the settings apply to the whole group, so they are set again for each part
+        if outcome.generator + outcome.solver > SLOW {
+            group.sample_size(SLOW_SAMPLES).sampling_mode(SamplingMode::Flat);
+        } else {
+            group.sample_size(SAMPLES).sampling_mode(SamplingMode::Auto);
+        }
+
+        group.bench_function(BenchmarkId::new("generator", &part), |b| {
+            b.iter(|| solver.generate(black_box(input)).unwrap())
+        });
+        let runner = solver.generate(input).unwrap();
+        group.bench_function(BenchmarkId::new("solver", &part), |b| {
+            b.iter(|| runner.try_run().unwrap())
+        });
+    }
+    group.finish();
+}
+
+
+fn solvers(c: &mut Criterion) {
+    for year in registry::years() {
+        for day in registry::days(year) {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("input/{}/day{}.txt", year, day));
+            if let Ok(input) = fs::read_to_string(path) {
+                bench_day(c, year, day, &input);
+            }
+        }
+    }
+}
+
+
+criterion_group!(benches, solvers);
+criterion_main!(benches);
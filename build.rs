@@ -0,0 +1,39 @@
+//! Lists the 2020 solvers for the registry, from the #[aoc(dayN, partM)] attributes
+//! in src/year2020. Writes year2020_solvers.rs to OUT_DIR, an expression of type &[Solver].
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+
+const DIR: &str = "src/year2020";
+
+
+/// Day and part of an #[aoc(dayN, partM)] attribute.
+fn attribute(line: &str) -> Option<(u32, u32)> {
+    let args = line.trim().strip_prefix("#[aoc(")?.strip_suffix(")]")?;
+    let mut args = args.split(',').map(|a| a.trim());
+    let day = args.next()?.strip_prefix("day")?.parse().ok()?;
+    let part = args.next()?.strip_prefix("part")?.parse().ok()?;
+    Some((day, part))
+}
+
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", DIR);
+    let mut parts = Vec::new();
+    for entry in fs::read_dir(DIR).expect("Cannot read the solver directory") {
+        let path = entry.unwrap().path();
+        println!("cargo:rerun-if-changed={}", path.display());
+        let text = fs::read_to_string(&path).unwrap();
+        parts.extend(text.lines().filter_map(attribute));
+    }
+    parts.sort_unstable();
+
+    let mut out = String::from("&[\n");
+    for (day, part) in parts {
+        out.push_str(&format!("    Solver {{ year: 2020, day: {0}, part: {1}, generator: Factory::day{0}_part{1} }},\n", day, part));
+    }
+    out.push(']');
+    fs::write(Path::new(&env::var("OUT_DIR").unwrap()).join("year2020_solvers.rs"), out).unwrap();
+}
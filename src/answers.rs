@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use toml::Value;
+
+
+/// Recorded puzzle answers by day and part, read from answers/<year>.toml:
+///
+/// ```toml
+/// [day13]
+/// part1 = 171
+/// part2 = "539746751134958"
+/// ```
+#[derive(Debug, PartialEq, Default)]
+pub struct Answers(HashMap<(u32, u32), String>);
+
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(PathBuf, io::Error),
+    Toml(toml::de::Error),
+    /// A key which is neither dayN nor partN, or a value which is neither string nor integer.
+    Invalid(String),
+}
+
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AnswersError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            AnswersError::Toml(e) => write!(f, "invalid answers file: {}", e),
+            AnswersError::Invalid(key) => write!(f, "invalid answer entry: {}", key),
+        }
+    }
+}
+
+
+impl Error for AnswersError {}
+
+
+/// Number from a key like day17 or part2.
+fn key_num(key: &str, prefix: &str) -> Option<u32> {
+    key.strip_prefix(prefix).and_then(|n| n.parse().ok())
+}
+
+
+impl Answers {
+    /// Location of the answers of a year, relative to dir.
+    pub fn path<P: AsRef<Path>>(dir: P, year: u32) -> PathBuf {
+        dir.as_ref().join("answers").join(format!("{}.toml", year))
+    }
+
+    /// Reads the answers of a year from the answers directory in dir.
+    pub fn load<P: AsRef<Path>>(dir: P, year: u32) -> Result<Answers, AnswersError> {
+        let path = Answers::path(dir, year);
+        let text = fs::read_to_string(&path).map_err(|e| AnswersError::Io(path, e))?;
+        Answers::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> Result<Answers, AnswersError> {
+        let table: toml::value::Table = toml::from_str(text).map_err(AnswersError::Toml)?;
+        let mut answers = HashMap::new();
+        for (day_key, parts) in table.iter() {
+            let day = key_num(day_key, "day").ok_or_else(|| AnswersError::Invalid(day_key.clone()))?;
+            let parts = parts.as_table().ok_or_else(|| AnswersError::Invalid(day_key.clone()))?;
+            for (part_key, value) in parts.iter() {
+                let invalid = || AnswersError::Invalid(format!("{}.{}", day_key, part_key));
+                let part = key_num(part_key, "part").ok_or_else(invalid)?;
+                let answer = match value {
+                    Value::String(s) => s.clone(),
+                    Value::Integer(i) => i.to_string(),
+                    _ => return Err(invalid()),
+                };
+                answers.insert((day, part), answer);
+            }
+        }
+        Ok(Answers(answers))
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.0.get(&(day, part)).map(|s| s.as_str())
+    }
+
+    /// All recorded answers as (day, part, answer), in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item=(u32, u32, &str)> {
+        self.0.iter().map(|(&(day, part), a)| (day, part, a.as_str()))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse("[day1]\npart1 = 514579\npart2 = \"241861950\"\n\n[day23]\npart1 = \"67384529\"").unwrap();
+        assert_eq!(answers.get(1, 1), Some("514579"));
+        assert_eq!(answers.get(1, 2), Some("241861950"));
+        assert_eq!(answers.get(23, 1), Some("67384529"));
+        assert_eq!(answers.get(23, 2), None);
+        assert!(matches!(Answers::parse("[dayx]\npart1 = 1"), Err(AnswersError::Invalid(_))));
+        assert!(matches!(Answers::parse("[day1]\npart1 = 1.5"), Err(AnswersError::Invalid(_))));
+        assert!(matches!(Answers::parse("[day1\n"), Err(AnswersError::Toml(_))));
+    }
+
+    #[test]
+    fn test_recorded_answers() {
+        let answers = Answers::load(env!("CARGO_MANIFEST_DIR"), 2020).unwrap();
+        assert_eq!(answers.get(17, 2), Some("1524"));
+    }
+}
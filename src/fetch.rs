@@ -0,0 +1,213 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+/// Environment variable with the session cookie of a logged in user.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+/// Environment variable overriding the base url, e.g. for a mirror or a mock server.
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// The input is not cached and there is no session token to download it.
+    NoSession(PathBuf),
+    Io(PathBuf, io::Error),
+    /// The server answered with an error status.
+    Status(u16, String),
+    /// The request did not get an answer.
+    Request(String),
+}
+
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FetchError::NoSession(path) => write!(f, "{} does not exist, set {} to download it", path.display(), SESSION_VAR),
+            FetchError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            FetchError::Status(code, url) => write!(f, "{} answered with status {}", url, code),
+            FetchError::Request(e) => write!(f, "request failed: {}", e),
+        }
+    }
+}
+
+
+impl Error for FetchError {}
+
+
+/// Provides puzzle inputs from input/<year>/day<day>.txt below a directory,
+/// downloading them first if they are not there yet. Cached inputs are never downloaded again.
+#[derive(Debug, Clone)]
+pub struct Fetcher {
+    dir: PathBuf,
+    base_url: String,
+    session: Option<String>,
+}
+
+
+impl Fetcher {
+    /// Fetcher for adventofcode.com without a session, it can only read cached inputs.
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Fetcher {
+        Fetcher { dir: dir.into(), base_url: DEFAULT_BASE_URL.to_string(), session: None }
+    }
+
+    /// Takes session and base url from the environment, see SESSION_VAR and BASE_URL_VAR.
+    pub fn from_env<P: Into<PathBuf>>(dir: P) -> Fetcher {
+        let mut fetcher = Fetcher::new(dir);
+        if let Ok(url) = env::var(BASE_URL_VAR) {
+            fetcher = fetcher.with_base_url(&url);
+        }
+        if let Ok(session) = env::var(SESSION_VAR) {
+            fetcher = fetcher.with_session(&session);
+        }
+        fetcher
+    }
+
+    pub fn with_base_url(mut self, url: &str) -> Fetcher {
+        self.base_url = url.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_session(mut self, session: &str) -> Fetcher {
+        self.session = Some(session.trim().to_string()).filter(|s| !s.is_empty());
+        self
+    }
+
+    pub fn url(&self, year: u32, day: u32) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, year, day)
+    }
+
+    pub fn path(&self, year: u32, day: u32) -> PathBuf {
+        self.dir.join("input").join(year.to_string()).join(format!("day{}.txt", day))
+    }
+
+    /// Whether the input is cached and can be read without a download.
+    pub fn is_cached(&self, year: u32, day: u32) -> bool {
+        self.path(year, day).is_file()
+    }
+
+    /// Reads the input, downloading it first if it is not cached.
+    pub fn input(&self, year: u32, day: u32) -> Result<String, FetchError> {
+        let path = self.path(year, day);
+        if !path.is_file() {
+            let text = self.download(year, day, &path)?;
+            save(&path, &text)?;
+            return Ok(text);
+        }
+        fs::read_to_string(&path).map_err(|e| FetchError::Io(path, e))
+    }
+
+    fn download(&self, year: u32, day: u32, path: &Path) -> Result<String, FetchError> {
+        let session = self.session.as_ref().ok_or_else(|| FetchError::NoSession(path.to_path_buf()))?;
+        let url = self.url(year, day);
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", session))
+            .set("User-Agent", concat!("aoc2020-rs/", env!("CARGO_PKG_VERSION")))
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::Status(code, _) => FetchError::Status(code, url.clone()),
+                e => FetchError::Request(e.to_string()),
+            })?;
+        response.into_string().map_err(|e| FetchError::Request(e.to_string()))
+    }
+}
+
+
+/// Writes to a temporary file first, so an interrupted write does not leave a partial input in the cache.
+fn save(path: &Path, text: &str) -> Result<(), FetchError> {
+    let io_err = |e| FetchError::Io(path.to_path_buf(), e);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io_err)?;
+    }
+    let tmp = path.with_extension("txt.part");
+    fs::write(&tmp, text).map_err(io_err)?;
+    fs::rename(&tmp, path).map_err(io_err)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+
+    /// Serves one response per expected request and reports the request lines and cookies.
+    fn mock_server(responses: Vec<(u16, &'static str)>) -> (String, mpsc::Receiver<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                reader.read_line(&mut request).unwrap();
+                let mut cookie = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(c) = line.strip_prefix("Cookie: ") {
+                        cookie = c.trim().to_string();
+                    }
+                }
+                tx.send((request.trim().to_string(), cookie)).unwrap();
+                write!(stream, "HTTP/1.1 {} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                       status, body.len(), body).unwrap();
+            }
+        });
+        (url, rx)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_url_and_path() {
+        let fetcher = Fetcher::new("/data").with_base_url("http://localhost:8080/");
+        assert_eq!(fetcher.url(2020, 7), "http://localhost:8080/2020/day/7/input");
+        assert_eq!(fetcher.path(2020, 7), PathBuf::from("/data/input/2020/day7.txt"));
+    }
+
+    #[test]
+    fn test_download_and_cache() {
+        let dir = temp_dir("cache");
+        let (url, requests) = mock_server(vec![(200, "1721\n979\n")]);
+        let fetcher = Fetcher::new(&dir).with_base_url(&url).with_session("abc");
+        assert!(!fetcher.is_cached(2020, 1));
+        assert_eq!(fetcher.input(2020, 1).unwrap(), "1721\n979\n");
+        assert_eq!(requests.recv().unwrap(), ("GET /2020/day/1/input HTTP/1.1".to_string(), "session=abc".to_string()));
+        assert!(fetcher.is_cached(2020, 1));
+        // the server only answers once, a second download would fail
+        assert_eq!(fetcher.input(2020, 1).unwrap(), "1721\n979\n");
+        assert!(requests.try_recv().is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_errors() {
+        let dir = temp_dir("errors");
+        let fetcher = Fetcher::new(&dir);
+        assert!(matches!(fetcher.input(2020, 4), Err(FetchError::NoSession(_))));
+        let (url, _requests) = mock_server(vec![(404, "not found")]);
+        let fetcher = fetcher.with_base_url(&url).with_session("abc");
+        match fetcher.input(2020, 4) {
+            Err(FetchError::Status(404, u)) => assert_eq!(u, format!("{}/2020/day/4/input", url)),
+            r => panic!("unexpected result {:?}", r),
+        }
+        assert!(!fetcher.is_cached(2020, 4));
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,335 @@
+use std::fmt;
+use std::iter;
+use std::ops::{Index, IndexMut};
+
+use crate::parse::{Input, ParseError};
+
+
+/// Offsets of the four orthogonal neighbours as (dx, dy), clockwise starting upwards.
+pub const DIRECTIONS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of the orthogonal and diagonal neighbours as (dx, dy), row by row.
+pub const DIRECTIONS_8: [(isize, isize); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0),
+                                               (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+
+/// Rectangular grid stored row by row in a flat buffer, indexed by (x, y).
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+
+/// One of the eight symmetries of a square: a number of clockwise quarter turns,
+/// applied after an optional left-right flip.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Orientation {
+    pub flipped: bool,
+    pub rotations: u8,
+}
+
+
+impl Orientation {
+    pub const IDENTITY: Orientation = Orientation { flipped: false, rotations: 0 };
+
+    /// All eight orientations, starting with the identity.
+    pub fn all() -> impl Iterator<Item=Orientation> {
+        [false, true].iter().flat_map(|&flipped| {
+            (0..4).map(move |rotations| Orientation { flipped, rotations })
+        })
+    }
+}
+
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(width * height, cells.len(), "Grid size does not match number of cells");
+        Grid { width, height, cells }
+    }
+
+    /// Builds a grid from rows of equal length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let height = rows.len();
+        let width = rows.first().map(|r| r.len()).unwrap_or(0);
+        assert!(rows.iter().all(|r| r.len() == width), "Rows differ in length");
+        Grid::new(width, height, rows.into_iter().flatten().collect())
+    }
+
+    /// Parses a char map with one row per line, f maps a char to a cell or returns None if the char is invalid.
+    /// The text has to be part of the input of src, errors are located in it.
+    pub fn parse<F: FnMut(char) -> Option<T>>(src: &Input, text: &str, expected: &str, mut f: F) -> Result<Grid<T>, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+        for line in text.lines() {
+            if line.is_empty() {
+                return Err(src.error(line, "a non-empty row"));
+            }
+            for (i, c) in line.char_indices() {
+                cells.push(f(c).ok_or_else(|| src.error(&line[i..i + c.len_utf8()], expected))?);
+            }
+            if height == 0 {
+                width = cells.len();
+            } else if cells.len() != (height + 1) * width {
+                return Err(src.error(line, &format!("a row of {} cells", width)));
+            }
+            height += 1;
+        }
+        if height == 0 {
+            return Err(src.error(&text[..0], "a non-empty row"));
+        }
+        Ok(Grid { width, height, cells })
+    }
+
+    /// Builds a grid by calling f for each (x, y).
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(width: usize, height: usize, mut f: F) -> Grid<T> {
+        let cells = (0..height).flat_map(|y| (0..width).map(move |x| (x, y)))
+                               .map(|(x, y)| f(x, y))
+                               .collect();
+        Grid { width, height, cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Cell at (x, y) of the grid repeated endlessly in both directions.
+    pub fn get_wrapped(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self.cells[y * self.width + x]
+    }
+
+    /// Position one step from (x, y) in direction (dx, dy), if it is inside the grid.
+    pub fn step(&self, (x, y): (usize, usize), (dx, dy): (isize, isize)) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        if x < self.width && y < self.height {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    /// Positions of the orthogonal neighbours inside the grid.
+    pub fn neighbours4(&self, pos: (usize, usize)) -> impl Iterator<Item=(usize, usize)> + '_ {
+        DIRECTIONS_4.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    /// Positions of the orthogonal and diagonal neighbours inside the grid.
+    pub fn neighbours8(&self, pos: (usize, usize)) -> impl Iterator<Item=(usize, usize)> + '_ {
+        DIRECTIONS_8.iter().filter_map(move |&d| self.step(pos, d))
+    }
+
+    /// Positions seen from pos looking in direction dir, up to the edge of the grid.
+    pub fn ray(&self, pos: (usize, usize), dir: (isize, isize)) -> impl Iterator<Item=(usize, usize)> + '_ {
+        iter::successors(self.step(pos, dir), move |&p| self.step(p, dir))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item=&T> {
+        assert!(x < self.width, "Column {} out of a grid of width {}", x, self.width);
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item=&[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=&T> {
+        self.cells.iter()
+    }
+
+    /// All positions (x, y), row by row.
+    pub fn positions(&self) -> impl Iterator<Item=(usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+
+impl<T: Clone> Grid<T> {
+    /// Returns the grid turned by 90 degrees clockwise.
+    pub fn rotated(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |x, y| self[(y, self.height - 1 - x)].clone())
+    }
+
+    /// Returns the grid mirrored left to right.
+    pub fn flipped(&self) -> Grid<T> {
+        Grid::from_fn(self.width, self.height, |x, y| self[(self.width - 1 - x, y)].clone())
+    }
+
+    pub fn transposed(&self) -> Grid<T> {
+        Grid::from_fn(self.height, self.width, |x, y| self[(y, x)].clone())
+    }
+
+    /// Returns the part of the grid with top left corner (x, y) and the given size.
+    pub fn cropped(&self, x: usize, y: usize, width: usize, height: usize) -> Grid<T> {
+        assert!(x + width <= self.width && y + height <= self.height, "Crop out of bounds");
+        Grid::from_fn(width, height, |i, j| self[(x + i, y + j)].clone())
+    }
+
+    pub fn oriented(&self, o: Orientation) -> Grid<T> {
+        let mut g = if o.flipped { self.flipped() } else { self.clone() };
+        for _ in 0..o.rotations % 4 {
+            g = g.rotated();
+        }
+        g
+    }
+
+    /// All eight orientations of the grid, in the order of Orientation::all.
+    pub fn orientations(&self) -> impl Iterator<Item=Grid<T>> + '_ {
+        Orientation::all().map(move |o| self.oriented(o))
+    }
+}
+
+
+/// Writes the grid as a char map, one line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width && y < self.height, "Index ({}, {}) out of bounds", x, y);
+        &self.cells[y * self.width + x]
+    }
+}
+
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width && y < self.height, "Index ({}, {}) out of bounds", x, y);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u8> {
+        Grid::from_rows(vec![vec![1, 2, 3],
+                             vec![4, 5, 6]])
+    }
+
+    #[test]
+    fn test_access() {
+        let g = grid();
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g[(2, 0)], 3);
+        assert_eq!(g.get(1, 1), Some(&5));
+        assert_eq!(g.get(3, 0), None);
+        assert_eq!(g.row(1), &[4, 5, 6]);
+        assert_eq!(g.column(1).cloned().collect::<Vec<u8>>(), vec![2, 5]);
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let text = "#.\n.#\n##";
+        let src = Input::new(1, text);
+        let bit = |c| match c {
+            '#' => Some(1),
+            '.' => Some(0),
+            _ => None,
+        };
+        let g = Grid::parse(&src, text, "'#' or '.'", bit).unwrap();
+        assert_eq!(g, Grid::from_rows(vec![vec![1, 0], vec![0, 1], vec![1, 1]]));
+        assert_eq!(g.to_string(), "10\n01\n11");
+        assert_eq!(g.map(|&b| if b == 1 { '#' } else { '.' }).to_string(), text);
+
+        let text = "#.\n.x";
+        let err = Grid::parse(&Input::new(1, text), text, "'#' or '.'", bit).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "x"));
+        let text = "#.\n.#.";
+        let err = Grid::parse(&Input::new(1, text), text, "'#' or '.'", bit).unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (2, "a row of 2 cells"));
+        let text = "\n\n";
+        let err = Grid::parse(&Input::new(1, text), text, "'#' or '.'", bit).unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (1, "a non-empty row"));
+        let text = "#.\n\n.#";
+        let err = Grid::parse(&Input::new(1, text), text, "'#' or '.'", bit).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, ""));
+        assert!(Grid::parse(&Input::new(1, ""), "", "'#' or '.'", bit).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of a grid of width 3")]
+    fn test_column_out_of_bounds() {
+        let _ = grid().column(3);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let g = grid();
+        assert_eq!(*g.get_wrapped(4, -1), 5);
+        assert_eq!(*g.get_wrapped(-3, 2), 1);
+        assert_eq!(g.step((0, 0), (-1, 0)), None);
+        assert_eq!(g.step((0, 0), (1, 1)), Some((1, 1)));
+        assert_eq!(g.neighbours4((0, 0)).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+        assert_eq!(g.neighbours8((1, 0)).count(), 5);
+        assert_eq!(g.neighbours8((1, 1)).map(|p| g[p]).collect::<Vec<_>>(), vec![1, 2, 3, 4, 6]);
+        assert_eq!(g.ray((0, 0), (1, 0)).collect::<Vec<_>>(), vec![(1, 0), (2, 0)]);
+        assert_eq!(g.ray((0, 1), (1, -1)).collect::<Vec<_>>(), vec![(1, 0)]);
+        assert_eq!(g.ray((2, 1), (0, 1)).count(), 0);
+        assert_eq!(g.rows().collect::<Vec<_>>(), vec![&[1, 2, 3][..], &[4, 5, 6][..]]);
+        assert_eq!(g.positions().nth(4), Some((1, 1)));
+    }
+
+    #[test]
+    fn test_transforms() {
+        let g = grid();
+        assert_eq!(g.rotated(), Grid::from_rows(vec![vec![4, 1], vec![5, 2], vec![6, 3]]));
+        assert_eq!(g.flipped(), Grid::from_rows(vec![vec![3, 2, 1], vec![6, 5, 4]]));
+        assert_eq!(g.transposed(), Grid::from_rows(vec![vec![1, 4], vec![2, 5], vec![3, 6]]));
+        assert_eq!(g.cropped(1, 0, 2, 2), Grid::from_rows(vec![vec![2, 3], vec![5, 6]]));
+        assert_eq!(g.rotated().rotated().rotated().rotated(), g);
+    }
+
+    #[test]
+    fn test_orientations() {
+        let g = Grid::from_rows(vec![vec![1, 2], vec![3, 4]]);
+        let all: Vec<Grid<u8>> = g.orientations().collect();
+        assert_eq!(all.len(), 8);
+        assert_eq!(all[0], g);
+        for (i, a) in all.iter().enumerate() {
+            assert!(all[i + 1..].iter().all(|b| a != b));
+        }
+        assert!(all.contains(&g.transposed()));
+    }
+}
@@ -1,5 +1,3 @@
-//#![feature(min_const_generics)]
-
 extern crate aoc_runner;
 
 #[macro_use]
@@ -8,24 +6,20 @@ extern crate aoc_runner_derive;
 extern crate regex;
 extern crate itertools;
 extern crate cached;
+extern crate toml;
+extern crate ureq;
+
+pub mod year2020;
 
-pub mod day1;
-pub mod day2;
-pub mod day3;
-//pub mod day4;
-pub mod day5;
-pub mod day6;
-//pub mod day7;
-pub mod day8;
-pub mod day9;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day17;
-pub mod day18;
-pub mod day19;
+pub mod answers;
+pub mod fetch;
+pub mod grid;
+pub mod life;
+pub mod numtheory;
+pub mod parse;
+pub mod registry;
 
+// aoc-runner supports a single year per crate, it generates the Factory used by
+// the registry for year2020. Other years can add their generator and solver
+// functions to the registry with prepared!.
 aoc_lib!{ year = 2020 }
@@ -0,0 +1,314 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+use crate::grid::Grid;
+
+
+/// Cell of a cellular automaton on an unbounded space.
+pub trait Cell: Eq + Hash + Clone {
+    fn neighbours(&self) -> Vec<Self>;
+}
+
+
+/// Life-like rule in B/S notation, e.g. B3/S23: inactive cells become active with a number
+/// of active neighbours listed after B, active cells stay active with a number listed after S.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Rule {
+    /// Bit n is set if n active neighbours are listed.
+    birth: u16,
+    survival: u16,
+}
+
+
+/// Sets the bit of each neighbour count, counts are single digits as in the notation.
+const fn mask(counts: &[usize]) -> u16 {
+    let mut m = 0;
+    let mut i = 0;
+    while i < counts.len() {
+        assert!(counts[i] <= 9, "Neighbour counts are single digits");
+        m |= 1 << counts[i];
+        i += 1;
+    }
+    m
+}
+
+
+impl Rule {
+    /// Conway's game of life, B3/S23, also the rule of the day17 pocket dimension.
+    pub const LIFE: Rule = Rule::new(&[3], &[2, 3]);
+    /// The day24 lobby floor, B2/S12.
+    pub const LOBBY: Rule = Rule::new(&[2], &[1, 2]);
+
+    pub const fn new(birth: &[usize], survival: &[usize]) -> Rule {
+        Rule { birth: mask(birth), survival: mask(survival) }
+    }
+
+    /// Whether a cell is active in the next generation.
+    pub fn next(&self, active: bool, neighbours: usize) -> bool {
+        let m = if active { self.survival } else { self.birth };
+        neighbours < 16 && m >> neighbours & 1 == 1
+    }
+}
+
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct RuleError(pub String);
+
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid rule {:?}, expected B<digits>/S<digits> like B3/S23", self.0)
+    }
+}
+
+
+impl Error for RuleError {}
+
+
+impl FromStr for Rule {
+    type Err = RuleError;
+
+    fn from_str(s: &str) -> Result<Rule, RuleError> {
+        let err = || RuleError(s.to_string());
+        let digits = |d: &str| d.chars().try_fold(0, |m, c| Some(m | 1 << c.to_digit(10)?));
+        let (birth, survival) = s.split_once('/').ok_or_else(err)?;
+        let birth = birth.strip_prefix('B').and_then(digits).ok_or_else(err)?;
+        let survival = survival.strip_prefix('S').and_then(digits).ok_or_else(err)?;
+        Ok(Rule { birth, survival })
+    }
+}
+
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = |m: u16| (0..10).filter(|n| m >> n & 1 == 1).map(|n| n.to_string()).collect::<String>();
+        write!(f, "B{}/S{}", digits(self.birth), digits(self.survival))
+    }
+}
+
+
+/// Applies a life-like rule to a sparse set of active cells.
+/// Rules with B0 are not allowed, they would activate the whole unbounded space.
+pub fn step<C: Cell>(state: &HashSet<C>, rule: Rule) -> HashSet<C> {
+    assert!(!rule.next(false, 0), "Rule {} activates cells without neighbours", rule);
+    let mut counts: HashMap<C, usize> = HashMap::new();
+    for active in state {
+        for n in active.neighbours() {
+            *counts.entry(n).or_insert(0) += 1;
+        }
+    }
+    let mut new_state = HashSet::new();
+    for active in state {
+        if rule.next(true, counts.get(active).cloned().unwrap_or(0)) {
+            new_state.insert(active.clone());
+        }
+    }
+    for (candidate, n) in counts {
+        if !state.contains(&candidate) && rule.next(false, n) {
+            new_state.insert(candidate);
+        }
+    }
+    new_state
+}
+
+
+/// State of a simulation which can be compared with earlier generations by a hash.
+pub trait Generation: Eq {
+    fn fingerprint(&self) -> u64;
+}
+
+
+fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+
+impl<T: Hash + Eq> Generation for Grid<T> {
+    fn fingerprint(&self) -> u64 {
+        hash_of(self)
+    }
+}
+
+
+/// The hash of a set does not depend on the order of iteration, so element hashes are summed.
+impl<C: Hash + Eq> Generation for HashSet<C> {
+    fn fingerprint(&self) -> u64 {
+        self.iter().fold(hash_of(&self.len()), |h, c| h.wrapping_add(hash_of(c)))
+    }
+}
+
+
+/// How a simulation ended.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Ending {
+    /// Generation start does not change anymore.
+    FixedPoint(usize),
+    /// Generation start + length is the same as generation start.
+    Cycle { start: usize, length: usize },
+    /// No generation repeated within the step limit.
+    StepLimit,
+}
+
+
+/// Generations of a simulation up to the first repetition.
+#[derive(Debug, Clone)]
+pub struct History<G> {
+    generations: Vec<G>,
+    ending: Ending,
+}
+
+
+impl<G> History<G> {
+    pub fn ending(&self) -> Ending {
+        self.ending
+    }
+
+    /// The last generation before the first repetition or the step limit.
+    pub fn last(&self) -> &G {
+        self.generations.last().unwrap()
+    }
+
+    /// Generation n, which can be far beyond the step limit if the simulation ended in a cycle.
+    pub fn get(&self, n: usize) -> Option<&G> {
+        match self.ending {
+            _ if n < self.generations.len() => self.generations.get(n),
+            Ending::FixedPoint(start) => self.generations.get(start),
+            Ending::Cycle { start, length } => self.generations.get(start + (n - start) % length),
+            Ending::StepLimit => None,
+        }
+    }
+}
+
+
+/// Applies step to init until a generation repeats or limit steps are done.
+pub fn run<G: Generation, F: FnMut(&G) -> G>(init: G, limit: usize, mut step: F) -> History<G> {
+    let mut seen: HashMap<u64, Vec<usize>> = HashMap::new();
+    seen.insert(init.fingerprint(), vec![0]);
+    let mut generations = vec![init];
+    while generations.len() <= limit {
+        let next = step(generations.last().unwrap());
+        let n = generations.len();
+        let same_hash = seen.entry(next.fingerprint()).or_default();
+        if let Some(&start) = same_hash.iter().find(|&&i| generations[i] == next) {
+            let ending = if n - start == 1 { Ending::FixedPoint(start) } else { Ending::Cycle { start, length: n - start } };
+            return History { generations, ending };
+        }
+        same_hash.push(n);
+        generations.push(next);
+    }
+    History { generations, ending: Ending::StepLimit }
+}
+
+
+/// Generation n, looking for a cycle during the first limit steps only, so at most
+/// limit + 1 generations are kept. Without a cycle the remaining steps are just applied.
+pub fn nth<G: Generation + Clone, F: FnMut(&G) -> G>(init: G, n: usize, limit: usize, mut step: F) -> G {
+    let history = run(init, n.min(limit), &mut step);
+    if let Some(g) = history.get(n) {
+        return g.clone();
+    }
+    let mut g = history.last().clone();
+    for _ in history.generations.len() - 1..n {
+        g = step(&g);
+    }
+    g
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+    struct P(i32, i32);
+
+    impl Cell for P {
+        fn neighbours(&self) -> Vec<P> {
+            let mut v = Vec::new();
+            for i in -1..=1 {
+                for j in -1..=1 {
+                    if i != 0 || j != 0 {
+                        v.push(P(self.0+i, self.1+j));
+                    }
+                }
+            }
+            v
+        }
+    }
+
+    fn cells(v: &[(i32, i32)]) -> HashSet<P> {
+        v.iter().map(|&(x, y)| P(x, y)).collect()
+    }
+
+    #[test]
+    fn test_blinker() {
+        let horizontal = cells(&[(0, 1), (1, 1), (2, 1)]);
+        let vertical = cells(&[(1, 0), (1, 1), (1, 2)]);
+        assert_eq!(step(&horizontal, Rule::LIFE), vertical);
+        assert_eq!(step(&vertical, Rule::LIFE), horizontal);
+    }
+
+    #[test]
+    fn test_survive_without_neighbours() {
+        let single = cells(&[(5, 5)]);
+        assert_eq!(step(&single, Rule::LIFE), HashSet::new());
+        assert_eq!(step(&single, "B/S0".parse().unwrap()), single);
+    }
+
+    #[test]
+    fn test_run() {
+        let blinker = run(cells(&[(0, 1), (1, 1), (2, 1)]), 100, |s| step(s, Rule::LIFE));
+        assert_eq!(blinker.ending(), Ending::Cycle { start: 0, length: 2 });
+        assert_eq!(blinker.get(1_000_000_001), Some(&cells(&[(1, 0), (1, 1), (1, 2)])));
+        assert_eq!(blinker.get(1_000_000_000), blinker.get(0));
+
+        let block = cells(&[(0, 0), (0, 1), (1, 0), (1, 1)]);
+        assert_eq!(run(block.clone(), 100, |s| step(s, Rule::LIFE)).ending(), Ending::FixedPoint(0));
+        let dying = run(cells(&[(0, 0), (5, 5)]), 100, |s| step(s, Rule::LIFE));
+        assert_eq!(dying.ending(), Ending::FixedPoint(1));
+        assert_eq!(dying.get(50), Some(&HashSet::new()));
+
+        let glider = run(cells(&[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]), 8, |s| step(s, Rule::LIFE));
+        assert_eq!(glider.ending(), Ending::StepLimit);
+        assert_eq!(glider.get(8), Some(&cells(&[(3, 2), (4, 3), (2, 4), (3, 4), (4, 4)])));
+        assert_eq!(glider.get(9), None);
+    }
+
+    #[test]
+    fn test_nth() {
+        let glider = cells(&[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        let moved = |d: i32| cells(&[(1 + d, d), (2 + d, 1 + d), (d, 2 + d), (1 + d, 2 + d), (2 + d, 2 + d)]);
+        assert_eq!(nth(glider.clone(), 40, 8, |s| step(s, Rule::LIFE)), moved(10));
+        assert_eq!(nth(glider, 8, 100, |s| step(s, Rule::LIFE)), moved(2));
+        let blinker = cells(&[(0, 1), (1, 1), (2, 1)]);
+        assert_eq!(nth(blinker.clone(), 1_000_000_000, 10, |s| step(s, Rule::LIFE)), blinker);
+    }
+
+    #[test]
+    fn test_set_fingerprint() {
+        let a: HashSet<u32> = (0..100).collect();
+        let b: HashSet<u32> = (0..100).rev().collect();
+        assert_eq!(a.fingerprint(), b.fingerprint());
+        assert_ne!(a.fingerprint(), (1..101).collect::<HashSet<u32>>().fingerprint());
+    }
+
+    #[test]
+    fn test_rule() {
+        assert_eq!("B3/S23".parse(), Ok(Rule::LIFE));
+        assert_eq!("B2/S21".parse(), Ok(Rule::LOBBY));
+        assert_eq!(Rule::LOBBY.to_string(), "B2/S12");
+        assert_eq!("B0/S".parse::<Rule>().unwrap().to_string(), "B0/S");
+        assert!(Rule::LIFE.next(false, 3) && !Rule::LIFE.next(false, 2));
+        assert!(Rule::LIFE.next(true, 2) && !Rule::LIFE.next(true, 4));
+        assert!(!Rule::LIFE.next(true, 100));
+        for s in &["B3S23", "3/S23", "B3/23", "B3/S2x", "B/S10 "] {
+            assert_eq!(s.parse::<Rule>(), Err(RuleError(s.to_string())));
+        }
+    }
+}
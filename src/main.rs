@@ -1,7 +1,483 @@
-extern crate aoc2020;
-extern crate aoc_runner_derive;
-extern crate aoc_runner;
+extern crate aoc;
 
-use aoc_runner_derive::aoc_main;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::process;
+use std::time::Duration;
 
-aoc_main! { lib = aoc2020 }
+use aoc::answers::Answers;
+use aoc::fetch::Fetcher;
+use aoc::registry::{self, Outcome, RunError, Solver};
+
+
+const USAGE: &str = "Usage: aoc [options] <command>
+
+Commands:
+    run <day> [part]    run a day, both parts if no part is given
+    run --all           run all days
+    bench [day] [part]  run solvers repeatedly and report their mean times
+    check               run all solvers and compare with answers/<year>.toml
+    list                list the registered days and parts
+    fetch <day>         download the input of a day unless it is cached
+    fetch --all         download all missing inputs
+
+Options:
+    --year <year>       puzzle year, defaults to 2020
+    --input <path|->    read the input from a file or stdin, instead of input/<year>/day<day>.txt
+    --format text|json  output format, defaults to text
+    --runs <n>          number of runs for bench, defaults to 10
+
+Missing inputs are downloaded to input/<year>/ if AOC_SESSION holds a session token,
+AOC_BASE_URL overrides the server.";
+
+
+/// Year used without --year.
+const DEFAULT_YEAR: u32 = 2020;
+
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Format {
+    Text,
+    Json,
+}
+
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    /// Runs one day, or all days if day is None
+    Run { day: Option<u32>, part: Option<u32> },
+    Bench { day: Option<u32>, part: Option<u32>, runs: u32 },
+    Check,
+    List,
+    /// Downloads one day, or all days if day is None
+    Fetch { day: Option<u32> },
+}
+
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    year: u32,
+    input: Option<String>,
+    format: Format,
+    command: Command,
+}
+
+
+fn parse_num(s: &str, what: &str) -> Result<u32, String> {
+    s.parse().map_err(|_| format!("invalid {}: {}", what, s))
+}
+
+
+fn parse_args<I: Iterator<Item=String>>(mut args: I) -> Result<Options, String> {
+    let mut year = DEFAULT_YEAR;
+    let mut input = None;
+    let mut format = Format::Text;
+    let mut runs = None;
+    let mut all = false;
+    let mut positional = Vec::new();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("{} needs a value", name));
+        match arg.as_str() {
+            "--year" => year = parse_num(&value("--year")?, "year")?,
+            "--input" => input = Some(value("--input")?),
+            "--format" => format = match value("--format")?.as_str() {
+                "text" => Format::Text,
+                "json" => Format::Json,
+                f => return Err(format!("unknown format: {}", f)),
+            },
+            "--runs" => runs = Some(parse_num(&value("--runs")?, "number of runs")?),
+            "--all" => all = true,
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            a if a.starts_with("--") => return Err(format!("unknown option: {}", a)),
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let command = positional.next().ok_or_else(|| USAGE.to_string())?;
+    let day = positional.next().map(|d| parse_num(&d, "day")).transpose()?;
+    let part = positional.next().map(|p| parse_num(&p, "part")).transpose()?;
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument: {}", extra));
+    }
+
+    let command = match command.as_str() {
+        "run" | "fetch" => match (day, all) {
+            (Some(_), true) => return Err(format!("{} takes either a day or --all", command)),
+            (None, false) => return Err(format!("{} needs a day or --all", command)),
+            _ if command == "run" => Command::Run { day, part },
+            _ if part.is_some() => return Err("fetch takes no part".to_string()),
+            _ => Command::Fetch { day },
+        },
+        "bench" => Command::Bench { day, part, runs: runs.unwrap_or(10) },
+        "check" => Command::Check,
+        "list" => Command::List,
+        c => return Err(format!("unknown command: {}", c)),
+    };
+    if let (Command::Check, Some(_)) | (Command::List, Some(_)) = (&command, day) {
+        return Err("unexpected argument after command".to_string());
+    }
+    if runs.is_some() && !matches!(command, Command::Bench { .. }) {
+        return Err("--runs is only valid for bench".to_string());
+    }
+    if input.is_some() {
+        match command {
+            Command::Run { day: Some(_), .. } | Command::Bench { day: Some(_), .. } => {},
+            _ => return Err("--input needs a single day".to_string()),
+        }
+    }
+
+    Ok(Options { year, input, format, command })
+}
+
+
+/// Reads the input of a day from --input or the input directory.
+fn read_input(opts: &Options, day: u32) -> Result<String, String> {
+    match opts.input.as_deref() {
+        Some("-") => {
+            let mut s = String::new();
+            io::stdin().read_to_string(&mut s).map_err(|e| e.to_string())?;
+            Ok(s)
+        },
+        Some(path) => fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e)),
+        None => Fetcher::from_env(".").input(opts.year, day).map_err(|e| e.to_string()),
+    }
+}
+
+
+/// Result of running a single solver.
+enum Status {
+    Solved(Outcome),
+    NoInput(String),
+    Failed(RunError),
+}
+
+
+struct Report {
+    solver: &'static Solver,
+    status: Status,
+}
+
+
+/// Solvers of a year selected by day and part, all solvers of the year if no day is given.
+fn select(year: u32, day: Option<u32>, part: Option<u32>) -> Result<Vec<&'static Solver>, String> {
+    let solvers: Vec<&Solver> = registry::for_year(year)
+        .filter(|s| day.is_none_or(|d| s.day == d) && part.is_none_or(|p| s.part == p))
+        .collect();
+    match (solvers.is_empty(), day, part) {
+        (true, Some(d), Some(p)) => Err(format!("no solver for day {} part {}", d, p)),
+        (true, Some(d), None) => Err(format!("no solver for day {}", d)),
+        _ => Ok(solvers),
+    }
+}
+
+
+/// Runs the solvers, reading the input of each day only once.
+fn run_solvers(opts: &Options, solvers: &[&'static Solver], runs: u32) -> Vec<Report> {
+    let mut reports = Vec::new();
+    let mut input: Option<(u32, Result<String, String>)> = None;
+    for &solver in solvers {
+        if input.as_ref().map(|i| i.0) != Some(solver.day) {
+            input = Some((solver.day, read_input(opts, solver.day)));
+        }
+        let status = match &input.as_ref().unwrap().1 {
+            Err(e) => Status::NoInput(e.clone()),
+            Ok(s) => mean_outcome(solver, s, runs),
+        };
+        reports.push(Report { solver, status });
+    }
+    reports
+}
+
+
+/// Runs a solver several times and averages the times, stops at the first failure.
+fn mean_outcome(solver: &Solver, input: &str, runs: u32) -> Status {
+    let runs = runs.max(1);
+    let mut outcome: Option<Outcome> = None;
+    let (mut generator, mut solve) = (Duration::default(), Duration::default());
+    for _ in 0..runs {
+        match solver.run(input) {
+            Ok(o) => {
+                generator += o.generator;
+                solve += o.solver;
+                outcome = Some(o);
+            },
+            Err(e) => return Status::Failed(e),
+        }
+    }
+    let mut outcome = outcome.unwrap();
+    outcome.generator = generator / runs;
+    outcome.solver = solve / runs;
+    Status::Solved(outcome)
+}
+
+
+fn json_str(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+
+fn json_report(year: u32, r: &Report) -> String {
+    let head = format!("{{\"year\": {}, \"day\": {}, \"part\": {}", year, r.solver.day, r.solver.part);
+    match &r.status {
+        Status::Solved(o) => format!("{}, \"status\": \"solved\", \"answer\": {}, \"generator_ns\": {}, \"solver_ns\": {}}}",
+                                     head, json_str(&o.answer), o.generator.as_nanos(), o.solver.as_nanos()),
+        Status::NoInput(e) => format!("{}, \"status\": \"no_input\", \"error\": {}}}", head, json_str(e)),
+        Status::Failed(e) => format!("{}, \"status\": \"failed\", \"error\": {}}}", head, json_str(&e.to_string())),
+    }
+}
+
+
+fn print_json(items: &[String]) {
+    println!("[");
+    for (i, item) in items.iter().enumerate() {
+        println!("  {}{}", item, if i + 1 < items.len() { "," } else { "" });
+    }
+    println!("]");
+}
+
+
+fn print_run(opts: &Options, reports: &[Report]) {
+    if opts.format == Format::Json {
+        return print_json(&reports.iter().map(|r| json_report(opts.year, r)).collect::<Vec<_>>());
+    }
+    println!("Advent of code {}", opts.year);
+    for r in reports {
+        let (day, part) = (r.solver.day, r.solver.part);
+        match &r.status {
+            Status::Solved(o) => println!("Day {} - Part {}: {}\n\tgenerator: {:?},\n\trunner: {:?}\n",
+                                          day, part, o.answer, o.generator, o.solver),
+            Status::NoInput(e) => eprintln!("Day {} - Part {}: no input ({})\n", day, part, e),
+            Status::Failed(e) => eprintln!("Day {} - Part {}: {}\n", day, part, e),
+        }
+    }
+}
+
+
+fn print_bench(opts: &Options, reports: &[Report], runs: u32) {
+    if opts.format == Format::Json {
+        return print_json(&reports.iter().map(|r| json_report(opts.year, r)).collect::<Vec<_>>());
+    }
+    println!("Mean of {} runs", runs);
+    for r in reports {
+        let (day, part) = (r.solver.day, r.solver.part);
+        match &r.status {
+            Status::Solved(o) => println!("Day {:>2} - Part {}: generator {:>12?}  solver {:>12?}",
+                                          day, part, o.generator, o.solver),
+            Status::NoInput(_) => println!("Day {:>2} - Part {}: no input", day, part),
+            Status::Failed(_) => println!("Day {:>2} - Part {}: failed", day, part),
+        }
+    }
+}
+
+
+/// Comparison of a report with the recorded answer.
+#[derive(Debug, PartialEq)]
+enum Verdict {
+    Pass,
+    Fail(String),
+    Missing(&'static str),
+}
+
+
+fn verdict(r: &Report, expected: Option<&str>) -> Verdict {
+    match (&r.status, expected) {
+        (Status::NoInput(_), _) => Verdict::Missing("no input"),
+        // only the headline of the error, diagnostics do not fit into the table
+        (Status::Failed(e), _) => Verdict::Fail(e.to_string().lines().take(2).collect::<Vec<_>>().join(" ")),
+        (Status::Solved(_), None) => Verdict::Missing("no recorded answer"),
+        (Status::Solved(o), Some(a)) if o.answer == a => Verdict::Pass,
+        (Status::Solved(o), Some(a)) => Verdict::Fail(format!("got {}, expected {}", o.answer, a)),
+    }
+}
+
+
+/// Prints the verdicts of all reports, returns whether none failed.
+fn print_check(opts: &Options, reports: &[Report], answers: &Answers) -> bool {
+    let verdicts: Vec<Verdict> = reports.iter()
+        .map(|r| verdict(r, answers.get(r.solver.day, r.solver.part)))
+        .collect();
+    if opts.format == Format::Json {
+        let items: Vec<String> = reports.iter().zip(verdicts.iter()).map(|(r, v)| {
+            let mut item = json_report(opts.year, r);
+            item.pop();
+            let (check, detail) = match v {
+                Verdict::Pass => ("pass", String::new()),
+                Verdict::Fail(d) => ("fail", d.clone()),
+                Verdict::Missing(d) => ("missing", d.to_string()),
+            };
+            format!("{}, \"check\": \"{}\", \"detail\": {}}}", item, check, json_str(&detail))
+        }).collect();
+        print_json(&items);
+    } else {
+        println!("day  part  check");
+        for (r, v) in reports.iter().zip(verdicts.iter()) {
+            let check = match v {
+                Verdict::Pass => "pass".to_string(),
+                Verdict::Fail(d) => format!("FAIL     {}", d),
+                Verdict::Missing(d) => format!("missing  {}", d),
+            };
+            println!("{:>3}  {:>4}  {}", r.solver.day, r.solver.part, check);
+        }
+        let count = |f: fn(&Verdict) -> bool| verdicts.iter().filter(|v| f(v)).count();
+        println!("\n{} passed, {} failed, {} missing",
+                 count(|v| *v == Verdict::Pass),
+                 count(|v| matches!(v, Verdict::Fail(_))),
+                 count(|v| matches!(v, Verdict::Missing(_))));
+    }
+    verdicts.iter().all(|v| !matches!(v, Verdict::Fail(_)))
+}
+
+
+fn print_list(opts: &Options) {
+    let days = registry::days(opts.year);
+    let parts = |d| registry::for_day(opts.year, d).map(|s| s.part.to_string()).collect::<Vec<_>>();
+    if opts.format == Format::Json {
+        let items: Vec<String> = days.iter().map(|&d| {
+            format!("{{\"year\": {}, \"day\": {}, \"parts\": [{}]}}", opts.year, d, parts(d).join(", "))
+        }).collect();
+        return print_json(&items);
+    }
+    for d in days {
+        println!("Day {:>2}: part {}", d, parts(d).join(", "));
+    }
+}
+
+
+fn fetch(opts: &Options, day: Option<u32>) -> Result<(), String> {
+    let fetcher = Fetcher::from_env(".");
+    let days: Vec<u32> = registry::days(opts.year).into_iter().filter(|&d| day.is_none_or(|x| x == d)).collect();
+    if days.is_empty() {
+        return Err(format!("no solver for day {}", day.unwrap_or(0)));
+    }
+    for d in days {
+        let cached = fetcher.is_cached(opts.year, d);
+        fetcher.input(opts.year, d).map_err(|e| e.to_string())?;
+        println!("Day {:>2}: {} {}", d, fetcher.path(opts.year, d).display(), if cached { "(cached)" } else { "(downloaded)" });
+    }
+    Ok(())
+}
+
+
+fn main() {
+    let opts = match parse_args(env::args().skip(1)) {
+        Ok(o) => o,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(2);
+        },
+    };
+    if !registry::years().contains(&opts.year) {
+        eprintln!("no solvers for year {}", opts.year);
+        process::exit(2);
+    }
+
+    let result = match opts.command {
+        Command::Run { day, part } => select(opts.year, day, part).map(|s| {
+            let reports = run_solvers(&opts, &s, 1);
+            print_run(&opts, &reports);
+            reports
+        }),
+        Command::Bench { day, part, runs } => select(opts.year, day, part).map(|s| {
+            let reports = run_solvers(&opts, &s, runs);
+            print_bench(&opts, &reports, runs);
+            reports
+        }),
+        Command::Check => select(opts.year, None, None).map(|s| {
+            let answers = Answers::load(".", opts.year).unwrap_or_else(|e| {
+                eprintln!("no recorded answers ({})", e);
+                Answers::default()
+            });
+            let reports = run_solvers(&opts, &s, 1);
+            if !print_check(&opts, &reports, &answers) {
+                process::exit(1);
+            }
+            reports
+        }),
+        Command::List => {
+            print_list(&opts);
+            Ok(Vec::new())
+        },
+        Command::Fetch { day } => fetch(&opts, day).map(|_| Vec::new()),
+    };
+    match result {
+        Ok(reports) if reports.iter().any(|r| matches!(r.status, Status::Failed(_))) => process::exit(1),
+        Ok(_) => {},
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(2);
+        },
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<Options, String> {
+        parse_args(s.split_whitespace().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(args("run 17 2").unwrap().command, Command::Run { day: Some(17), part: Some(2) });
+        assert_eq!(args("run --all").unwrap().command, Command::Run { day: None, part: None });
+        let opts = args("--format json run 3 --input - --year 2020").unwrap();
+        assert_eq!((opts.format, opts.input.as_deref(), opts.year), (Format::Json, Some("-"), 2020));
+        assert_eq!(args("bench 15 --runs 3").unwrap().command, Command::Bench { day: Some(15), part: None, runs: 3 });
+        assert_eq!(args("check").unwrap().command, Command::Check);
+        assert_eq!(args("list").unwrap().command, Command::List);
+        assert_eq!(args("fetch 4").unwrap().command, Command::Fetch { day: Some(4) });
+        assert_eq!(args("fetch --all").unwrap().command, Command::Fetch { day: None });
+        assert_eq!(args("list").unwrap().year, 2020);
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(args("").is_err());
+        assert!(args("run").is_err());
+        assert!(args("run 3 --all").is_err());
+        assert!(args("run x").is_err());
+        assert!(args("run --all --input day3.txt").is_err());
+        assert!(args("check --runs 2").is_err());
+        assert!(args("list --format xml").is_err());
+        assert!(args("solve 1").is_err());
+        assert!(args("fetch 4 1").is_err());
+        assert!(args("fetch").is_err());
+        assert!(args("list --year x").is_err());
+    }
+
+    #[test]
+    fn test_verdict() {
+        let solver = registry::find(2020, 1, 1).unwrap();
+        let solved = Report { solver, status: mean_outcome(solver, "1721\n299", 1) };
+        assert_eq!(verdict(&solved, Some("514579")), Verdict::Pass);
+        assert_eq!(verdict(&solved, Some("1")), Verdict::Fail("got 514579, expected 1".to_string()));
+        assert_eq!(verdict(&solved, None), Verdict::Missing("no recorded answer"));
+        let failed = Report { solver, status: mean_outcome(solver, "x", 1) };
+        assert!(matches!(verdict(&failed, Some("1")), Verdict::Fail(d) if d.starts_with("failed while generating: day 1")));
+        let missing = Report { solver, status: Status::NoInput(String::new()) };
+        assert_eq!(verdict(&missing, Some("1")), Verdict::Missing("no input"));
+    }
+
+    #[test]
+    fn test_json_str() {
+        assert_eq!(json_str("a\"b\\c\nd"), "\"a\\\"b\\\\c\\nd\"");
+        assert_eq!(json_str("\u{1}"), "\"\\u0001\"");
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+
+/// Extended euclidean algorithm.
+/// Returns (g, x, y) with g = gcd(a, b) and a*x + b*y = g.
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = ext_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+
+/// Inverse of a modulo m, if a and m are coprime.
+pub fn mod_inv(a: u64, m: u64) -> Option<u64> {
+    let (g, x, _) = ext_gcd(a as i128, m as i128);
+    if g == 1 {
+        Some(x.rem_euclid(m as i128) as u64)
+    } else {
+        None
+    }
+}
+
+
+/// Computes base^exp mod m by square and multiply.
+pub fn mod_pow(base: u64, mut exp: u64, m: u64) -> u64 {
+    let m = m as u128;
+    let mut base = base as u128 % m;
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result as u64
+}
+
+
+/// Combines x = a1 (mod n1) and x = a2 (mod n2) into x = a (mod lcm(n1, n2)).
+/// The moduli do not have to be coprime, returns None if the congruences contradict each other.
+fn crt_pair((a1, n1): (i128, i128), (a2, n2): (i128, i128)) -> Option<(i128, i128)> {
+    let (g, p, _) = ext_gcd(n1, n2);
+    if (a2 - a1) % g != 0 {
+        return None;
+    }
+    let lcm = n1 / g * n2;
+    let k = ((a2 - a1) / g * p).rem_euclid(n2 / g);
+    Some(((a1 + k * n1).rem_euclid(lcm), lcm))
+}
+
+
+/// Solves a system of congruences (remainder, modulus) with the chinese remainder theorem.
+/// Returns the smallest non-negative solution and the modulus of the combined congruence.
+pub fn crt<I: IntoIterator<Item=(i128, i128)>>(congruences: I) -> Option<(i128, i128)> {
+    congruences.into_iter().try_fold((0, 1), crt_pair)
+}
+
+
+/// Tries all exponents in order, works for any base but takes up to m steps.
+fn discrete_log_naive(base: u64, target: u64, m: u64) -> Option<u64> {
+    let base = base as u128 % m as u128;
+    let target = target as u128 % m as u128;
+    let mut value = 1 % m as u128;
+    for x in 0..m {
+        if value == target {
+            return Some(x);
+        }
+        value = value * base % m as u128;
+    }
+    None
+}
+
+
+/// Baby-step giant-step: writes x = i*n + j with n = ceil(sqrt(m)),
+/// stores base^j for all j and steps through target * base^(-n*i).
+/// Needs base to be invertible modulo m.
+fn baby_step_giant_step(base: u64, target: u64, m: u64) -> Option<u64> {
+    let n = (m as f64).sqrt().ceil() as u64;
+    let mut baby_steps = HashMap::new();
+    let mut value = 1 % m;
+    for j in 0..n {
+        // keep the smallest exponent, so the first match is the smallest solution
+        baby_steps.entry(value).or_insert(j);
+        value = (value as u128 * base as u128 % m as u128) as u64;
+    }
+    let giant_step = mod_pow(mod_inv(base, m)?, n, m);
+    let mut gamma = target % m;
+    for i in 0..n {
+        if let Some(j) = baby_steps.get(&gamma) {
+            return Some(i * n + j);
+        }
+        gamma = (gamma as u128 * giant_step as u128 % m as u128) as u64;
+    }
+    None
+}
+
+
+/// Smallest x with base^x = target (mod m), if there is any.
+/// Uses baby-step giant-step if base and m are coprime and falls back to trying all exponents otherwise.
+pub fn discrete_log(base: u64, target: u64, m: u64) -> Option<u64> {
+    if mod_inv(base, m).is_some() {
+        baby_step_giant_step(base, target, m)
+    } else {
+        discrete_log_naive(base, target, m)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ext_gcd() {
+        assert_eq!(ext_gcd(240, 46), (2, -9, 47));
+        assert_eq!(ext_gcd(7, 13).0, 1);
+    }
+
+    #[test]
+    fn test_mod_inv() {
+        assert_eq!(mod_inv(3, 11), Some(4));
+        assert_eq!(mod_inv(10, 17), Some(12));
+        assert_eq!(mod_inv(6, 9), None);
+    }
+
+    #[test]
+    fn test_mod_pow() {
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(7, 0, 13), 1);
+        assert_eq!(mod_pow(7, 0, 1), 0);
+        assert_eq!(mod_pow(17807724, 8, 20201227), 14897079);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(vec![(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // moduli not coprime
+        assert_eq!(crt(vec![(0, 4), (4, 6)]), Some((4, 12)));
+        assert_eq!(crt(vec![(0, 2), (1, 4)]), None);
+    }
+
+    #[test]
+    fn test_discrete_log() {
+        assert_eq!(discrete_log(7, 5764801, 20201227), Some(8));
+        assert_eq!(discrete_log(7, 17807724, 20201227), Some(11));
+        assert_eq!(discrete_log(3, 13, 17), Some(4));
+        // 2 has order 3 modulo 7, so 3 is never reached
+        assert_eq!(discrete_log(2, 3, 7), None);
+        // base and modulus not coprime
+        assert_eq!(discrete_log(2, 8, 12), Some(3));
+        assert_eq!(discrete_log(2, 3, 12), None);
+    }
+
+    #[test]
+    fn test_discrete_log_matches_naive() {
+        for m in 2..60 {
+            for base in 0..m {
+                for target in 0..m {
+                    assert_eq!(discrete_log(base, target, m), discrete_log_naive(base, target, m));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+
+/// Error in the puzzle input of a day, pointing at the offending text.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseError {
+    pub day: u32,
+    /// Line number, starting at 1
+    pub line: usize,
+    /// Column of the first offending character, starting at 1
+    pub column: usize,
+    /// The offending text, empty if something is missing
+    pub text: String,
+    /// What was expected instead
+    pub expected: String,
+    /// The whole line containing the error
+    pub source: String,
+}
+
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "day {}, line {}, column {}: expected {}, ", self.day, self.line, self.column, self.expected)?;
+        if self.text.is_empty() {
+            writeln!(f, "found nothing")?;
+        } else {
+            writeln!(f, "found \"{}\"", self.text)?;
+        }
+        let margin = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{} |", margin)?;
+        writeln!(f, "{} | {}", self.line, self.source)?;
+        write!(f, "{} | {}{}", margin, " ".repeat(self.column - 1), "^".repeat(self.text.chars().count().max(1)))
+    }
+}
+
+
+impl Error for ParseError {}
+
+
+/// Puzzle input of a day, used to locate parse errors.
+/// The parts passed to its methods have to be slices of the input text,
+/// anything else is reported at the end of the input.
+#[derive(Debug, Clone, Copy)]
+pub struct Input<'a> {
+    day: u32,
+    text: &'a str,
+}
+
+
+impl<'a> Input<'a> {
+    pub fn new(day: u32, text: &'a str) -> Input<'a> {
+        Input { day, text }
+    }
+
+    /// Byte offset of part in the input.
+    fn offset(&self, part: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let pos = part.as_ptr() as usize;
+        if pos >= start && pos + part.len() <= start + self.text.len() {
+            pos - start
+        } else {
+            self.text.len()
+        }
+    }
+
+    /// Error pointing at part of the input.
+    /// Use an empty slice at the end of the preceding text to report something missing.
+    pub fn error(&self, part: &str, expected: &str) -> ParseError {
+        let offset = self.offset(part);
+        let line_start = self.text[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = self.text[offset..].find('\n').map_or(self.text.len(), |i| offset + i);
+        let source = self.text[line_start..line_end].trim_end_matches('\r');
+        // errors spanning several lines only show the first one
+        let text = part.lines().next().unwrap_or("").trim_end_matches('\r');
+        ParseError {
+            day: self.day,
+            line: self.text[..offset].matches('\n').count() + 1,
+            column: self.text[line_start..offset].chars().count() + 1,
+            text: text.to_string(),
+            expected: expected.to_string(),
+            source: source.to_string(),
+        }
+    }
+
+    /// Parses part of the input, reporting it as error if it does not parse.
+    pub fn parse<T: FromStr>(&self, part: &str, expected: &str) -> Result<T, ParseError> {
+        part.parse().map_err(|_| self.error(part, expected))
+    }
+
+    /// Unwraps a part which may be missing, missing parts are reported at the end of after.
+    pub fn expect<'b>(&self, part: Option<&'b str>, after: &str, expected: &str) -> Result<&'b str, ParseError> {
+        part.ok_or_else(|| self.error(&after[after.len()..], expected))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "1-3 a: abcde\n1-3 bx: cdefg\n";
+
+    #[test]
+    fn test_location() {
+        let input = Input::new(2, TEXT);
+        let err = input.error(&TEXT[17..19], "a single letter");
+        assert_eq!((err.day, err.line, err.column), (2, 2, 5));
+        assert_eq!(err.text, "bx");
+        assert_eq!(err.source, "1-3 bx: cdefg");
+        assert_eq!(err.to_string(), "day 2, line 2, column 5: expected a single letter, found \"bx\"
+  |
+2 | 1-3 bx: cdefg
+  |     ^^");
+    }
+
+    #[test]
+    fn test_missing() {
+        let input = Input::new(8, "nop +0\nacc");
+        let line = input.text.lines().nth(1).unwrap();
+        let err = input.expect(line.split(' ').nth(1), line, "an argument").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.text, "");
+        assert!(err.to_string().ends_with("2 | acc\n  |    ^"));
+        // text outside of the input is reported at its end
+        assert_eq!(input.error("nop", "x").column, 4);
+    }
+
+    #[test]
+    fn test_parse() {
+        let input = Input::new(1, "12\nx3");
+        assert_eq!(input.parse::<u32>(&input.text[..2], "a number"), Ok(12));
+        let err = input.parse::<u32>(&input.text[3..], "a number").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "x3"));
+    }
+}
@@ -0,0 +1,209 @@
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::time::{Duration, Instant};
+
+use aoc_runner::{ArcStr, Runner};
+
+// Factory and the DayNPartM traits are generated by aoc_lib! at the crate root
+use crate::*;
+
+
+/// Runs the generator of a day and part on the input, returns the runner for the solver.
+pub type Generator = fn(ArcStr) -> Result<Box<dyn Runner>, Box<dyn Error>>;
+
+
+/// A registered solver, the generator and solve function of one part of a day.
+#[derive(Clone, Copy)]
+pub struct Solver {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+    generator: Generator,
+}
+
+
+impl fmt::Debug for Solver {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Solver({} day {}, part {})", self.year, self.day, self.part)
+    }
+}
+
+
+/// Answer of a solver with the time spent in the generator and in the solver.
+#[derive(Debug)]
+pub struct Outcome {
+    pub answer: String,
+    pub generator: Duration,
+    pub solver: Duration,
+}
+
+
+/// Error of a solver, by the stage it failed in.
+#[derive(Debug)]
+pub enum RunError {
+    Generator(Box<dyn Error>),
+    Solver(Box<dyn Error>),
+}
+
+
+impl fmt::Display for RunError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RunError::Generator(e) => write!(f, "failed while generating:\n{}", e),
+            RunError::Solver(e) => write!(f, "failed while running:\n{}", e),
+        }
+    }
+}
+
+
+impl Error for RunError {}
+
+
+impl Solver {
+    /// Runs only the generator, the returned runner applies the solver to the generated input.
+    pub fn generate(&self, input: &str) -> Result<Box<dyn Runner>, Box<dyn Error>> {
+        (self.generator)(ArcStr::from(input))
+    }
+
+    /// Runs generator and solver on the input and times both.
+    pub fn run(&self, input: &str) -> Result<Outcome, RunError> {
+        let start_time = Instant::now();
+        let runner = self.generate(input).map_err(RunError::Generator)?;
+        let inter_time = Instant::now();
+        let answer = runner.try_run().map_err(RunError::Solver)?;
+        let final_time = Instant::now();
+        Ok(Outcome {
+            answer: answer.to_string(),
+            generator: inter_time - start_time,
+            solver: final_time - inter_time,
+        })
+    }
+}
+
+
+/// Generated input and solve function of a part registered without aoc-runner.
+pub struct Prepared<T> {
+    pub input: T,
+    pub solve: fn(&T) -> Box<dyn Display>,
+}
+
+
+impl<T> Runner for Prepared<T> {
+    fn gen(_: ArcStr) -> Self {
+        unreachable!("Prepared runners are built by prepared!")
+    }
+
+    fn run(&self) -> Box<dyn Display> {
+        (self.solve)(&self.input)
+    }
+
+    fn bench(&self, black_box: fn(&dyn Display)) {
+        black_box(&*self.run())
+    }
+}
+
+
+/// Builds a Generator from a generator function taking &str and returning a Result,
+/// and a solver function taking a reference to the generated input.
+/// This is how years without aoc-runner support register their solvers.
+#[allow(unused_macros)]
+macro_rules! prepared {
+    ($gen:path, $solve:path) => {
+        |input: ArcStr| -> Result<Box<dyn Runner>, Box<dyn Error>> {
+            let input = $gen(std::borrow::Borrow::<str>::borrow(&input))?;
+            Ok(Box::new(Prepared { input, solve: |i| Box::new($solve(i)) }))
+        }
+    };
+}
+
+
+/// Solvers of the aoc attributes in year2020, listed by build.rs.
+const YEAR2020: &[Solver] = include!(concat!(env!("OUT_DIR"), "/year2020_solvers.rs"));
+
+
+/// Solvers by year, in ascending order.
+const YEARS: &[&[Solver]] = &[YEAR2020];
+
+
+/// All solvers, ordered by year, day and part.
+pub fn all() -> impl Iterator<Item=&'static Solver> {
+    YEARS.iter().flat_map(|y| y.iter())
+}
+
+
+/// The solver for a part of a day, if there is one.
+pub fn find(year: u32, day: u32, part: u32) -> Option<&'static Solver> {
+    all().find(|s| s.year == year && s.day == day && s.part == part)
+}
+
+
+/// All solvers of a day.
+pub fn for_day(year: u32, day: u32) -> impl Iterator<Item=&'static Solver> {
+    all().filter(move |s| s.year == year && s.day == day)
+}
+
+
+/// All solvers of a year.
+pub fn for_year(year: u32) -> impl Iterator<Item=&'static Solver> {
+    all().filter(move |s| s.year == year)
+}
+
+
+/// Years with at least one solver, in ascending order.
+pub fn years() -> Vec<u32> {
+    let mut years: Vec<u32> = all().map(|s| s.year).collect();
+    years.dedup();
+    years
+}
+
+
+/// Days of a year with at least one solver, in ascending order.
+pub fn days(year: u32) -> Vec<u32> {
+    let mut days: Vec<u32> = for_year(year).map(|s| s.day).collect();
+    days.dedup();
+    days
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        assert!((1..=25).all(|d| find(2020, d, 1).is_some()));
+        assert_eq!(for_day(2020, 25).count(), 1);
+        assert!(find(2020, 26, 1).is_none());
+        assert!(find(2019, 1, 1).is_none());
+        assert_eq!(years(), vec![2020]);
+        assert_eq!(days(2020), (1..=25).collect::<Vec<_>>());
+        assert!((1..=24).all(|d| for_day(2020, d).count() == 2));
+        let keys: Vec<(u32, u32, u32)> = all().map(|s| (s.year, s.day, s.part)).collect();
+        assert!(keys.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_run() {
+        let outcome = find(2020, 1, 1).unwrap().run("1721\n979\n366\n299\n675\n1456\n").unwrap();
+        assert_eq!(outcome.answer, "514579");
+        let err = find(2020, 1, 1).unwrap().run("1721\nx").unwrap_err();
+        assert!(matches!(err, RunError::Generator(_)));
+        assert!(err.to_string().starts_with("failed while generating:\nday 1, line 2, column 1"));
+    }
+
+    fn parse_numbers(input: &str) -> Result<Vec<u32>, std::num::ParseIntError> {
+        input.lines().map(|l| l.parse()).collect()
+    }
+
+    fn sum(input: &[u32]) -> u32 {
+        input.iter().sum()
+    }
+
+    #[test]
+    fn test_run_prepared() {
+        let solver = Solver { year: 2021, day: 1, part: 1, generator: prepared!(parse_numbers, sum) };
+        assert_eq!(solver.run("1\n2\n3\n").unwrap().answer, "6");
+        assert!(matches!(solver.run("1\nx"), Err(RunError::Generator(_))));
+        assert_eq!(solver.generate("4").unwrap().try_run().unwrap().to_string(), "4");
+    }
+}
@@ -1,8 +1,11 @@
 use itertools::iproduct;
 
+use crate::parse::{Input, ParseError};
+
 #[aoc_generator(day1)]
-pub fn parse_entries(input: &str) -> Vec<i32> {
-    input.lines().map(|l| l.parse::<i32>().unwrap()).collect()
+pub fn parse_entries(input: &str) -> Result<Vec<i32>, ParseError> {
+    let src = Input::new(1, input);
+    input.lines().map(|l| src.parse(l, "a number")).collect()
 }
 
 #[aoc(day1, part1)]
@@ -28,7 +31,7 @@ mod tests {
     #[test]
     fn test_parser() {
         let test_input = "1721\n979\n366\n299\n675\n1456\n";
-        assert_eq!(parse_entries(test_input), vec![1721, 979, 366, 299, 675, 1456]);
+        assert_eq!(parse_entries(test_input).unwrap(), vec![1721, 979, 366, 299, 675, 1456]);
     }
 
     #[test]
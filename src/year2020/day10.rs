@@ -1,14 +1,17 @@
 use itertools::izip;
 use cached::proc_macro::cached;
 
+use crate::parse::{Input, ParseError};
+
 
 #[aoc_generator(day10)]
-fn parse_nums(input: &str) -> Vec<u64> {
-    input.lines().map(|l| l.parse().unwrap()).collect()
+fn parse_nums(input: &str) -> Result<Vec<u64>, ParseError> {
+    let src = Input::new(10, input);
+    input.lines().map(|l| src.parse(l, "a number")).collect()
 }
 
 fn get_diff(input: &[u64]) -> Vec<u64> {
-    let mut s: Vec<u64> = input.iter().map(|i| *i).collect();
+    let mut s: Vec<u64> = input.to_vec();
     s.sort();
     s.insert(0, 0);
     izip!(s.iter(), s[1..].iter()).map(|(&i, &j)| j-i).collect()
@@ -75,7 +78,7 @@ mod tests {
     #[test]
     fn test_parser() {
         let input = "0\n15";
-        assert_eq!(parse_nums(input), vec![0, 15]);
+        assert_eq!(parse_nums(input).unwrap(), vec![0, 15]);
     }
 
     #[test]
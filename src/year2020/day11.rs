@@ -0,0 +1,230 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::grid::{Grid, DIRECTIONS_8};
+use crate::life::Rule;
+use crate::parse::{Input, ParseError};
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum Cell {
+    Floor,
+    Free,
+    Occupied,
+}
+
+impl Cell {
+    fn from_char(c: char) -> Option<Cell> {
+        match c {
+            '.' => Some(Cell::Floor),
+            'L' => Some(Cell::Free),
+            '#' => Some(Cell::Occupied),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let c = match self {
+            Cell::Floor => '.',
+            Cell::Free => 'L',
+            Cell::Occupied => '#',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+#[aoc_generator(day11)]
+fn parse_map(input: &str) -> Result<Grid<Cell>, ParseError> {
+    let src = Input::new(11, input);
+    Grid::parse(&src, input, "'.', 'L' or '#'", Cell::from_char)
+}
+
+fn count_neighbors(map: &Grid<Cell>, pos: (usize, usize)) -> usize {
+    map.neighbours8(pos).filter(|&p| map[p] == Cell::Occupied).count()
+}
+
+/// Returns the first seat seen from pos looking in direction dir.
+fn first_visible(map: &Grid<Cell>, pos: (usize, usize), dir: (isize, isize)) -> Option<&Cell> {
+    map.ray(pos, dir).map(|p| &map[p]).find(|&c| c != &Cell::Floor)
+}
+
+fn count_visible(map: &Grid<Cell>, pos: (usize, usize)) -> usize {
+    DIRECTIONS_8.iter().filter(|&&d| first_visible(map, pos, d) == Some(&Cell::Occupied)).count()
+}
+
+/// The seats that influence a seat: the adjacent ones or the first seat seen in each direction.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Neighbourhood {
+    Adjacent,
+    Visible,
+}
+
+use Neighbourhood::*;
+
+/// Life-like rule on the seats, with occupied seats as active cells. Floor never changes.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SeatRule {
+    pub rule: Rule,
+    pub neighbourhood: Neighbourhood,
+}
+
+impl SeatRule {
+    /// Part 1, B0/S0123 adjacent: people leave seats with four or more occupied neighbours.
+    pub const ADJACENT: SeatRule = SeatRule { rule: Rule::new(&[0], &[0, 1, 2, 3]), neighbourhood: Adjacent };
+    /// Part 2, B0/S01234 visible: people leave seats with five or more occupied seats in sight.
+    pub const VISIBLE: SeatRule = SeatRule { rule: Rule::new(&[0], &[0, 1, 2, 3, 4]), neighbourhood: Visible };
+
+    fn count(&self, map: &Grid<Cell>, pos: (usize, usize)) -> usize {
+        match self.neighbourhood {
+            Adjacent => count_neighbors(map, pos),
+            Visible => count_visible(map, pos),
+        }
+    }
+}
+
+impl fmt::Display for SeatRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.rule, if self.neighbourhood == Adjacent { "adjacent" } else { "visible" })
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct SeatRuleError(pub String);
+
+impl fmt::Display for SeatRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid seat rule {:?}, expected a B/S rule and adjacent or visible like B0/S0123 adjacent", self.0)
+    }
+}
+
+impl Error for SeatRuleError {}
+
+impl FromStr for SeatRule {
+    type Err = SeatRuleError;
+
+    fn from_str(s: &str) -> Result<SeatRule, SeatRuleError> {
+        let err = || SeatRuleError(s.to_string());
+        let (rule, neighbourhood) = s.split_once(' ').ok_or_else(err)?;
+        let neighbourhood = match neighbourhood {
+            "adjacent" => Adjacent,
+            "visible" => Visible,
+            _ => return Err(err()),
+        };
+        Ok(SeatRule { rule: rule.parse().map_err(|_| err())?, neighbourhood })
+    }
+}
+
+fn step(old: &Grid<Cell>, rule: SeatRule) -> Grid<Cell> {
+    Grid::from_fn(old.width(), old.height(), |x, y| match old[(x, y)] {
+        Cell::Floor => Cell::Floor,
+        ref c => {
+            let occupied = *c == Cell::Occupied;
+            if rule.rule.next(occupied, rule.count(old, (x, y))) { Cell::Occupied } else { Cell::Free }
+        },
+    })
+}
+
+fn count_occ(map: &Grid<Cell>) -> usize {
+    map.iter().filter(|&c| *c == Cell::Occupied).count()
+}
+
+/// Seating rounds after which the rules are assumed to never settle.
+const STEP_LIMIT: usize = 10_000;
+
+/// Runs the seating rules until the map does not change anymore,
+/// returns the number of occupied seats in the final state or None if it never settles.
+/// Only a fixed point matters, so each map is compared with the previous one alone
+/// instead of keeping the history.
+fn simulate(input: &Grid<Cell>, rule: SeatRule) -> Option<usize> {
+    let mut map = input.clone();
+    for _ in 0..STEP_LIMIT {
+        let next = step(&map, rule);
+        if next == map {
+            return Some(count_occ(&map));
+        }
+        map = next;
+    }
+    None
+}
+
+
+#[aoc(day11, part1)]
+pub fn solve_part1(input: &Grid<Cell>) -> Option<usize> {
+    simulate(input, SeatRule::ADJACENT)
+}
+
+#[aoc(day11, part2)]
+pub fn solve_part2(input: &Grid<Cell>) -> Option<usize> {
+    simulate(input, SeatRule::VISIBLE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "L.LL.LL.LL
+LLLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLLL
+L.LLLLLL.L
+L.LLLLL.LL";
+
+    #[test]
+    fn test_parser() {
+        let input = "LL\n.#";
+        assert_eq!(parse_map(input).unwrap(), Grid::from_rows(vec![vec![Cell::Free, Cell::Free],
+                                                             vec![Cell::Floor, Cell::Occupied]]));
+        assert_eq!(parse_map(input).unwrap().to_string(), input);
+    }
+
+    #[test]
+    fn test_step() {
+        let map = parse_map(TEST_INPUT).unwrap();
+        assert_eq!(step(&map, SeatRule::ADJACENT).to_string(), TEST_INPUT.replace('L', "#"));
+        assert_eq!(SeatRule::VISIBLE.to_string(), "B0/S01234 visible");
+        // nobody ever leaves a seat
+        let rule = SeatRule { rule: "B0/S012345678".parse().unwrap(), neighbourhood: Adjacent };
+        assert_eq!(simulate(&map, rule), Some(TEST_INPUT.matches('L').count()));
+        // a lone seat is taken and left again in every round
+        let rule = SeatRule { rule: "B0/S".parse().unwrap(), neighbourhood: Adjacent };
+        assert_eq!(simulate(&parse_map(".L.").unwrap(), rule), None);
+    }
+
+    #[test]
+    fn test_seat_rule() {
+        for rule in [SeatRule::ADJACENT, SeatRule::VISIBLE] {
+            assert_eq!(rule.to_string().parse(), Ok(rule));
+        }
+        assert_eq!("B0/S0123 adjacent".parse(), Ok(SeatRule::ADJACENT));
+        let rule: SeatRule = "B01/S2 visible".parse().unwrap();
+        assert_eq!((rule.rule, rule.neighbourhood), (Rule::new(&[0, 1], &[2]), Visible));
+        assert_eq!("B0/S0123".parse::<SeatRule>(), Err(SeatRuleError("B0/S0123".to_string())));
+        assert!("B0/S0123 diagonal".parse::<SeatRule>().is_err());
+        assert!("B0S0123 adjacent".parse::<SeatRule>().is_err());
+        assert!("B0/S0123  adjacent".parse::<SeatRule>().is_err());
+    }
+
+    #[test]
+    fn test_part1_solver() {
+        assert_eq!(solve_part1(&parse_map(TEST_INPUT).unwrap()), Some(37));
+    }
+
+    #[test]
+    fn test_count_visible() {
+        let map = parse_map(".......#.\n...#.....\n.#.......\n.........\n..#L....#\n....#....\n.........\n#........\n...#.....").unwrap();
+        assert_eq!(count_visible(&map, (3, 4)), 8);
+        let map = parse_map(".##.##.\n#.#.#.#\n##...##\n...L...\n##...##\n#.#.#.#\n.##.##.").unwrap();
+        assert_eq!(count_visible(&map, (3, 3)), 0);
+    }
+
+    #[test]
+    fn test_part2_solver() {
+        assert_eq!(solve_part2(&parse_map(TEST_INPUT).unwrap()), Some(26));
+    }
+}
@@ -1,3 +1,5 @@
+use crate::parse::{Input, ParseError};
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Dir {
     N,
@@ -14,12 +16,12 @@ impl Dir {
         match self { N => 0, E => 1, S => 2, W => 3 }
     }
 
-    fn from_num(&self, i: i64) -> Dir {
+    fn from_num(i: i64) -> Dir {
         match i { 0 => N, 1 => E, 2 => S, 3 => W, _ => panic!("Invalid num for dir") }
     }
 
     fn rotated_by_num(&self, num: i64) -> Dir {
-        self.from_num((self.to_num()+num)%4)
+        Dir::from_num((self.to_num()+num)%4)
     }
 
     fn rotated(&self, angle: i64) -> Dir {
@@ -91,14 +93,25 @@ impl Ship {
 }
 
 #[aoc_generator(day12)]
-fn parse_directions(input: &str) -> Vec<(char, i64)> {
-    input.lines().map(|l| (l.chars().next().unwrap(), l[1..].parse().unwrap())).collect()
+fn parse_directions(input: &str) -> Result<Vec<(char, i64)>, ParseError> {
+    let src = Input::new(12, input);
+    input.lines().map(|l| {
+        let (action, arg) = l.split_at(l.chars().next().map_or(0, char::len_utf8));
+        if !["N", "S", "E", "W", "L", "R", "F"].contains(&action) {
+            return Err(src.error(action, "one of N, S, E, W, L, R, F"));
+        }
+        let value = src.parse(arg, "a number")?;
+        if (action == "L" || action == "R") && ![0, 90, 180, 270, 360].contains(&value) {
+            return Err(src.error(arg, "one of 0, 90, 180, 270, 360 degrees"));
+        }
+        Ok((action.chars().next().unwrap(), value))
+    }).collect()
 }
 
 #[aoc(day12, part1)]
 pub fn solve_part1(input: &[(char, i64)]) -> i64 {
     let mut s = Ship(E, 0, 0);
-    for &(c, i) in input.into_iter() {
+    for &(c, i) in input.iter() {
         match c {
             'N' => s.mov(N, i),
             'S' => s.mov(S, i),
@@ -118,7 +131,7 @@ pub fn solve_part1(input: &[(char, i64)]) -> i64 {
 pub fn solve_part2(input: &[(char, i64)]) -> i64 {
     let mut s = Ship(E, 0, 0);
     let mut wp = Ship(E, 1, 10);
-    for &(c, i) in input.into_iter() {
+    for &(c, i) in input.iter() {
         match c {
             'N' => wp.mov(N, i),
             'S' => wp.mov(S, i),
@@ -137,21 +150,34 @@ pub fn solve_part2(input: &[(char, i64)]) -> i64 {
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &'static str = "F10\nN3\nF7\nR90\nF11";
+    const TEST_INPUT: &str = "F10\nN3\nF7\nR90\nF11";
 
     #[test]
     fn test_parser() {
         let input = "F10\nN3";
-        assert_eq!(parse_directions(input), vec![('F', 10), ('N', 3)]);
+        assert_eq!(parse_directions(input).unwrap(), vec![('F', 10), ('N', 3)]);
+    }
+
+    #[test]
+    fn test_parser_errors() {
+        let err = parse_directions("F10\nX3").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "X"));
+        let err = parse_directions("R45").unwrap_err();
+        assert_eq!((err.column, err.expected.as_str()), (2, "one of 0, 90, 180, 270, 360 degrees"));
+        let err = parse_directions("R-90\nF10").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 2, "-90"));
+        assert_eq!(parse_directions("F10\nL450").unwrap_err().text, "450");
+        assert_eq!(parse_directions("L0\nR360").unwrap(), vec![('L', 0), ('R', 360)]);
+        assert_eq!(parse_directions("N").unwrap_err().text, "");
     }
 
     #[test]
     fn test_part1_solver() {
-        assert_eq!(solve_part1(&parse_directions(TEST_INPUT)), 25);
+        assert_eq!(solve_part1(&parse_directions(TEST_INPUT).unwrap()), 25);
     }
 
     #[test]
     fn test_part2_solver() {
-        assert_eq!(solve_part2(&parse_directions(TEST_INPUT)), 286);
+        assert_eq!(solve_part2(&parse_directions(TEST_INPUT).unwrap()), 286);
     }
 }
@@ -0,0 +1,82 @@
+use crate::numtheory::crt;
+use crate::parse::{Input, ParseError};
+
+
+#[aoc_generator(day13)]
+fn parse_notes(input: &str) -> Result<(u64, Vec<Option<u64>>), ParseError> {
+    let src = Input::new(13, input);
+    let mut lines = input.lines();
+    let first = src.expect(lines.next(), input, "a timestamp")?;
+    let a = src.parse(first, "a timestamp")?;
+    let ids = src.expect(lines.next(), first, "a line of bus ids")?;
+    let buses: Vec<Option<u64>> = ids.split(',').map(|s| match s {
+        "x" => Ok(None),
+        o => match src.parse(o, "a bus id or x")? {
+            0 => Err(src.error(o, "a bus id other than 0")),
+            id => Ok(Some(id)),
+        },
+    }).collect::<Result<_, ParseError>>()?;
+    if buses.iter().all(Option::is_none) {
+        return Err(src.error(ids, "at least one bus id"));
+    }
+    Ok((a, buses))
+}
+
+
+#[aoc(day13, part1)]
+pub fn solve_part1(input: &(u64, Vec<Option<u64>>)) -> u64 {
+    // minutes from the timestamp to the next departure, 0 if a bus leaves right then
+    input.1.iter().flatten()
+                  .map(|&id| (id, (id - input.0 % id) % id))
+                  .min_by_key(|t| t.1).map(|t| t.0 * t.1).unwrap()
+}
+
+
+#[aoc(day13, part2)]
+pub fn solve_part2(input: &(u64, Vec<Option<u64>>)) -> Option<u64> {
+    // bus id departs at t + offset, so t = -offset (mod id)
+    let congruences = input.1.iter().enumerate().filter_map(|(offset, oid)| {
+        oid.map(|id| (-(offset as i128), id as i128))
+    });
+    crt(congruences).map(|(t, _)| t as u64)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "939\n7,13,x,x,59,x,31,19";
+
+    #[test]
+    fn test_parser() {
+        let input = "939\n7,x";
+        assert_eq!(parse_notes(input).unwrap(), (939, vec![Some(7), None]));
+    }
+
+    #[test]
+    fn test_parser_errors() {
+        let err = parse_notes("939\n7,0,x").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "0"));
+        let err = parse_notes("939\nx,x").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (2, 1, "at least one bus id"));
+    }
+
+    #[test]
+    fn test_part1_solver() {
+        assert_eq!(solve_part1(&parse_notes(TEST_INPUT).unwrap()), 295);
+        assert_eq!(solve_part1(&parse_notes("0\n7,13").unwrap()), 0);
+        assert_eq!(solve_part1(&parse_notes("15\n7,13").unwrap()), 7*6);
+    }
+
+    #[test]
+    fn test_part2_solver() {
+        assert_eq!(solve_part2(&parse_notes(TEST_INPUT).unwrap()), Some(1068781));
+        assert_eq!(solve_part2(&parse_notes("0\n17,x,13,19").unwrap()), Some(3417));
+        assert_eq!(solve_part2(&parse_notes("0\n67,7,59,61").unwrap()), Some(754018));
+        assert_eq!(solve_part2(&parse_notes("0\n67,x,7,59,61").unwrap()), Some(779210));
+        assert_eq!(solve_part2(&parse_notes("0\n67,7,x,59,61").unwrap()), Some(1261476));
+        assert_eq!(solve_part2(&parse_notes("0\n1789,37,47,1889").unwrap()), Some(1202161486));
+        assert_eq!(solve_part2(&parse_notes("0\n2,4").unwrap()), None);
+    }
+}
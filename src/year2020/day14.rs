@@ -1,6 +1,8 @@
 use std::str::FromStr;
 use std::collections::HashMap;
 
+use crate::parse::{Input, ParseError};
+
 
 #[derive(Debug, PartialEq)]
 pub struct Mask {
@@ -20,15 +22,16 @@ impl FromStr for Mask {
     type Err = char;
 
     /// Parses bitmask from 'X01'-pattern, leading Xs can be skiped
+    /// Returns the first invalid character as error.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let (and, or) = s.chars().rev().enumerate()
             .map(|(i, c)| match c {
-                '0' => (1 << i, 0),
-                '1' => (0, 1 << i),
-                'X' => (0, 0),
-                _ => { panic!("Invalid mask") }, //TODO: return Err(_) instead of panicing
-            }).fold((0, 0), |(a, b), (c, d)| (a+c, b+d));
-        Ok(Mask{ and: !and, or: or })
+                '0' => Ok((1 << i, 0)),
+                '1' => Ok((0, 1 << i)),
+                'X' => Ok((0, 0)),
+                _ => Err(c),
+            }).try_fold((0, 0), |(a, b), r| r.map(|(c, d)| (a+c, b+d)))?;
+        Ok(Mask{ and: !and, or })
     }
 }
 
@@ -41,22 +44,24 @@ pub enum Instr {
 
 
 #[aoc_generator(day14)]
-fn parse_prog(input: &str) -> Vec<Instr> {
+fn parse_prog(input: &str) -> Result<Vec<Instr>, ParseError> {
+    let src = Input::new(14, input);
     let mut instr = Vec::new();
     for line in input.lines() {
-        if line.starts_with("mask") {
-            instr.push(Instr::Mask(line[7..].parse().unwrap()));
-        } else if line.starts_with("mem") {
-            let mut parts = line.split('=').map(|s| s.trim());
+        if let Some(mask) = line.strip_prefix("mask = ") {
+            instr.push(Instr::Mask(src.parse(mask, "a mask of X, 0 and 1")?));
+        } else if let Some(assign) = line.strip_prefix("mem[") {
+            let mut parts = assign.splitn(2, "] = ");
+            let addr = parts.next().unwrap();
+            let value = src.expect(parts.next(), line, "mem[<address>] = <value>")?;
             instr.push(Instr::Assign(
-                (parts.next().unwrap().split('[').last().unwrap().split(']').next().unwrap().parse().unwrap(),
-                 parts.next().unwrap().parse().unwrap())
+                (src.parse(addr, "an address")?, src.parse(value, "a number")?)
             ));
         } else {
-            panic!("Invalid input");
+            return Err(src.error(line, "mask = <mask> or mem[<address>] = <value>"));
         }
     }
-    instr
+    Ok(instr)
 }
 
 
@@ -90,7 +95,7 @@ impl Mask {
     /// leaves bits where mask is zero untouched.
     /// Floating bit are set to zero.
     fn apply_to2(&self, num: u64) -> u64 {
-        (num | ((0 & self.and) | self.or)) & (!self.and | self.or)
+        (num | self.or) & (!self.and | self.or)
     }
 
     /// Returns iterator over all combinations alowed by floating bits.
@@ -98,7 +103,7 @@ impl Mask {
         // Only 36 bits
         let floating = (self.and & !self.or) & 0b111111111111111111111111111111111111;
         let fixed = self.apply_to2(addr as u64);
-        Floating{ fixed: fixed, floating: floating, count: 0 }
+        Floating{ fixed, floating, count: 0 }
     }
 }
 
@@ -165,11 +170,11 @@ pub fn solve_part2(input: &[Instr]) -> u64 {
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &'static str = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+    const TEST_INPUT: &str = "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
 mem[8] = 11
 mem[7] = 101
 mem[8] = 0";
-    const TEST_INPUT2: &'static str = "mask = 000000000000000000000000000000X1001X
+    const TEST_INPUT2: &str = "mask = 000000000000000000000000000000X1001X
 mem[42] = 100
 mask = 00000000000000000000000000000000X0XX
 mem[26] = 1";
@@ -189,12 +194,22 @@ mem[26] = 1";
                           Instr::Assign((8, 11)),
                           Instr::Assign((7, 101)),
                           Instr::Assign((8, 0))];
-        assert_eq!(parse_prog(TEST_INPUT), expect);
+        assert_eq!(parse_prog(TEST_INPUT).unwrap(), expect);
+    }
+
+    #[test]
+    fn test_parser_errors() {
+        assert_eq!("X1Y0".parse::<Mask>(), Err('Y'));
+        let err = parse_prog("mask = X1Y0\nmem[8] = 11").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 8, "X1Y0"));
+        let err = parse_prog("mem[8] = 11\nmem[a] = 1").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 5, "a"));
+        assert_eq!(parse_prog("mem[8] 11").unwrap_err().column, 10);
     }
 
     #[test]
     fn test_part1_solver() {
-        assert_eq!(solve_part1(&parse_prog(TEST_INPUT)), 165);
+        assert_eq!(solve_part1(&parse_prog(TEST_INPUT).unwrap()), 165);
     }
 
     #[test]
@@ -235,6 +250,6 @@ mem[26] = 1";
 
     #[test]
     fn test_part2_solver() {
-        assert_eq!(solve_part2(&parse_prog(TEST_INPUT2)), 208);
+        assert_eq!(solve_part2(&parse_prog(TEST_INPUT2).unwrap()), 208);
     }
 }
@@ -1,33 +1,37 @@
-use std::collections::HashMap;
+use crate::parse::{Input, ParseError};
 
 
 #[aoc_generator(day15)]
-fn parse_nums(input: &str) -> Vec<u64> {
-    input.split(',').map(|s| s.parse().unwrap()).collect()
+fn parse_nums(input: &str) -> Result<Vec<u64>, ParseError> {
+    let src = Input::new(15, input);
+    input.trim_end().split(',').map(|s| src.parse(s, "a number")).collect()
 }
 
 
+/// The number spoken in turn n is at most n, so the turn each number was last spoken
+/// fits into a flat table indexed by number instead of a hash map.
 fn play(input: &[u64], until: u64) -> u64 {
-    let mut nums: HashMap<u64, u64> = HashMap::new();
+    let size = input.iter().map(|&n| n as usize + 1).max().unwrap_or(0).max(until as usize);
+    // 0 means not spoken yet, turns start at 1
+    let mut last: Vec<u32> = vec![0; size];
     let mut turn = 1;
     for n in input.iter() {
-        nums.insert(*n, turn);
+        last[*n as usize] = turn;
         turn += 1;
     }
 
     let mut cur = 0;
     let mut next = 0;
-    while turn < until {
-        if let Some(n) = nums.get(&cur) {
-            next = turn - n;
-        } else {
-            next = 0;
-        }
-        nums.insert(cur, turn);
+    while turn < until as u32 {
+        next = match last[cur] {
+            0 => 0,
+            n => (turn - n) as usize,
+        };
+        last[cur] = turn;
         turn += 1;
         cur = next;
     }
-    next
+    next as u64
 }
 
 
@@ -49,7 +53,7 @@ mod tests {
 
     #[test]
     fn test_parser() {
-        assert_eq!(parse_nums("1,2,3"), vec![1, 2, 3]);
+        assert_eq!(parse_nums("1,2,3").unwrap(), vec![1, 2, 3]);
     }
 
     #[test]
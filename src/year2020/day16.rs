@@ -0,0 +1,288 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+use crate::parse::{Input, ParseError};
+
+
+#[derive(Debug, PartialEq)]
+pub struct FieldRule {
+    name: String,
+    ranges: Vec<(u64, u64)>,
+}
+
+
+impl FieldRule {
+    fn allows(&self, v: u64) -> bool {
+        self.ranges.iter().any(|&(low, high)| low <= v && v <= high)
+    }
+}
+
+
+#[derive(Debug, PartialEq)]
+pub struct Notes {
+    rules: Vec<FieldRule>,
+    mine: Vec<u64>,
+    nearby: Vec<Vec<u64>>,
+}
+
+
+#[derive(Debug, PartialEq)]
+pub enum AssignError {
+    /// No assignment satisfies all tickets.
+    Impossible,
+    /// More than one assignment is possible, contains the fields without a unique column.
+    Ambiguous(Vec<String>),
+}
+
+
+impl fmt::Display for AssignError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssignError::Impossible => write!(f, "no assignment of fields to columns fits all tickets"),
+            AssignError::Ambiguous(fields) => write!(f, "ambiguous columns for fields: {}", fields.join(", ")),
+        }
+    }
+}
+
+
+impl Error for AssignError {}
+
+
+fn parse_ticket(src: &Input, line: &str) -> Result<Vec<u64>, ParseError> {
+    line.split(',').map(|s| src.parse(s, "a number")).collect()
+}
+
+
+#[aoc_generator(day16)]
+fn parse_notes(input: &str) -> Result<Notes, ParseError> {
+    let src = Input::new(16, input);
+    let mut parts = input.split("\n\n");
+    let rule_part = parts.next().unwrap();
+    let rules = rule_part.lines().map(|l| {
+        let mut sp = l.split(": ");
+        let name = sp.next().unwrap().to_string();
+        let ranges = src.expect(sp.next(), l, "<field>: <ranges>")?.split(" or ").map(|r| {
+            let mut bounds = r.split('-');
+            let low = src.parse(bounds.next().unwrap(), "a number")?;
+            let high = src.parse(src.expect(bounds.next(), r, "<low>-<high>")?, "a number")?;
+            Ok((low, high))
+        }).collect::<Result<_, ParseError>>()?;
+        Ok(FieldRule{ name, ranges })
+    }).collect::<Result<_, ParseError>>()?;
+    let mine_part = src.expect(parts.next(), input, "your ticket")?;
+    let mine = parse_ticket(&src, src.expect(mine_part.lines().nth(1), mine_part, "your ticket")?)?;
+    let nearby = src.expect(parts.next(), input, "nearby tickets")?
+        .lines().skip(1).map(|l| {
+            let ticket = parse_ticket(&src, l)?;
+            if ticket.len() != mine.len() {
+                return Err(src.error(l, &format!("a ticket with {} values like yours", mine.len())));
+            }
+            Ok(ticket)
+        }).collect::<Result<_, ParseError>>()?;
+    Ok(Notes{ rules, mine, nearby })
+}
+
+
+#[aoc(day16, part1)]
+pub fn solve_part1(input: &Notes) -> u64 {
+    input.nearby.iter().flatten()
+        .filter(|&&v| !input.rules.iter().any(|r| r.allows(v)))
+        .sum()
+}
+
+
+/// Candidate columns for each field.
+type Candidates = Vec<HashSet<usize>>;
+
+
+/// Removes candidates by bipartite elimination: a field with a single column takes it away from
+/// all other fields. If there are as many fields as columns, every column needs a field,
+/// so a column that fits a single field is assigned to it.
+/// Returns false if a field is left without any column.
+fn propagate(cands: &mut Candidates, columns: usize) -> bool {
+    loop {
+        let mut changed = false;
+        for i in 0..cands.len() {
+            if cands[i].len() == 1 {
+                let col = *cands[i].iter().next().unwrap();
+                for (j, other) in cands.iter_mut().enumerate() {
+                    changed |= j != i && other.remove(&col);
+                }
+            }
+        }
+        if cands.len() == columns {
+            for col in 0..columns {
+                let mut fields = (0..cands.len()).filter(|&i| cands[i].contains(&col));
+                if let (Some(i), None) = (fields.next(), fields.next()) {
+                    if cands[i].len() > 1 {
+                        cands[i] = [col].iter().cloned().collect();
+                        changed = true;
+                    }
+                }
+            }
+        }
+        if cands.iter().any(|c| c.is_empty()) {
+            return false;
+        }
+        if !changed {
+            return true;
+        }
+    }
+}
+
+
+/// Backtracking search for assignments, stops after finding two of them.
+fn search(mut cands: Candidates, columns: usize, found: &mut Vec<Vec<usize>>) {
+    if !propagate(&mut cands, columns) {
+        return;
+    }
+    let open = (0..cands.len()).filter(|&i| cands[i].len() > 1).min_by_key(|&i| cands[i].len());
+    match open {
+        None => {
+            found.push(cands.iter().map(|c| *c.iter().next().unwrap()).collect());
+        },
+        Some(i) => {
+            let mut cols: Vec<usize> = cands[i].iter().cloned().collect();
+            cols.sort_unstable();
+            for col in cols {
+                let mut guess = cands.clone();
+                guess[i] = [col].iter().cloned().collect();
+                search(guess, columns, found);
+                if found.len() > 1 {
+                    return;
+                }
+            }
+        },
+    }
+}
+
+
+/// Assigns each field rule the column it describes, using the nearby tickets which are valid.
+/// Returns the column index for each rule.
+fn assign_fields(notes: &Notes) -> Result<Vec<usize>, AssignError> {
+    let valid: Vec<&Vec<u64>> = notes.nearby.iter()
+        .filter(|t| t.iter().all(|&v| notes.rules.iter().any(|r| r.allows(v))))
+        .collect();
+    let cands: Candidates = notes.rules.iter().map(|r| {
+        (0..notes.mine.len()).filter(|&col| valid.iter().all(|t| r.allows(t[col]))).collect()
+    }).collect();
+
+    let mut found = Vec::new();
+    search(cands, notes.mine.len(), &mut found);
+    match found.len() {
+        0 => Err(AssignError::Impossible),
+        1 => Ok(found.pop().unwrap()),
+        _ => Err(AssignError::Ambiguous(notes.rules.iter().enumerate()
+            .filter(|&(i, _)| found[0][i] != found[1][i])
+            .map(|(_, r)| r.name.clone())
+            .collect())),
+    }
+}
+
+
+#[aoc(day16, part2)]
+pub fn solve_part2(input: &Notes) -> Result<u64, AssignError> {
+    let columns = assign_fields(input)?;
+    Ok(input.rules.iter().zip(columns)
+        .filter(|(r, _)| r.name.starts_with("departure"))
+        .map(|(_, col)| input.mine[col])
+        .product())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12";
+
+    const TEST_INPUT2: &str = "class: 0-1 or 4-19
+row: 0-5 or 8-19
+seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
+
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9";
+
+    #[test]
+    fn test_parser() {
+        let notes = parse_notes(TEST_INPUT).unwrap();
+        assert_eq!(notes.rules[0], FieldRule{ name: "class".to_string(), ranges: vec![(1, 3), (5, 7)] });
+        assert_eq!(notes.mine, vec![7, 1, 14]);
+        assert_eq!(notes.nearby.len(), 4);
+        let short = TEST_INPUT.replace("40,4,50", "40,4");
+        let err = parse_notes(&short).unwrap_err();
+        assert_eq!((err.text.as_str(), err.expected.as_str()), ("40,4", "a ticket with 3 values like yours"));
+    }
+
+    #[test]
+    fn test_part1_solver() {
+        assert_eq!(solve_part1(&parse_notes(TEST_INPUT).unwrap()), 71);
+    }
+
+    #[test]
+    fn test_assign_fields() {
+        assert_eq!(assign_fields(&parse_notes(TEST_INPUT2).unwrap()), Ok(vec![1, 0, 2]));
+    }
+
+    #[test]
+    fn test_search() {
+        let set = |v: &[usize]| v.iter().cloned().collect::<HashSet<usize>>();
+        // propagation cannot decide anything, the search has to guess
+        let cands = vec![set(&[0, 1]), set(&[1, 2]), set(&[2, 3]), set(&[3, 0])];
+        let mut found = Vec::new();
+        search(cands, 4, &mut found);
+        assert_eq!(found, vec![vec![0, 1, 2, 3], vec![1, 2, 3, 0]]);
+        // propagation is stuck again, and guessing column 2 for field 1 leaves
+        // fields 2 and 3 with only column 1
+        let cands = vec![set(&[0, 2, 3]), set(&[2, 4]), set(&[1, 2]), set(&[1, 2]), set(&[0, 3, 4])];
+        let mut stuck = cands.clone();
+        assert!(propagate(&mut stuck, 5) && stuck == cands);
+        let mut guess = cands.clone();
+        guess[1] = set(&[2]);
+        assert!(!propagate(&mut guess, 5));
+        let mut found = Vec::new();
+        search(cands, 5, &mut found);
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|f| f[1] == 4));
+    }
+
+    #[test]
+    fn test_propagate_more_columns() {
+        let set = |v: &[usize]| v.iter().cloned().collect::<HashSet<usize>>();
+        // column 1 fits only the first field, but it does not need a field
+        let mut cands = vec![set(&[0, 1]), set(&[0, 2])];
+        assert!(propagate(&mut cands, 3));
+        assert_eq!(cands, vec![set(&[0, 1]), set(&[0, 2])]);
+        let mut found = Vec::new();
+        search(cands, 3, &mut found);
+        assert_eq!(found.len(), 2);
+    }
+
+    #[test]
+    fn test_assign_fields_ambiguous() {
+        let notes = parse_notes("a: 1-5 or 7-7\nb: 1-5 or 8-8\nc: 6-6 or 9-9\n\nyour ticket:\n1,2,6\n\nnearby tickets:\n3,4,9").unwrap();
+        let err = assign_fields(&notes).unwrap_err();
+        assert_eq!(err, AssignError::Ambiguous(vec!["a".to_string(), "b".to_string()]));
+        assert_eq!(err.to_string(), "ambiguous columns for fields: a, b");
+        // a and b both only fit the first column
+        let notes = parse_notes("a: 1-1 or 3-3\nb: 1-1 or 3-3\nc: 5-5 or 7-7\n\nyour ticket:\n1,5,5\n\nnearby tickets:\n1,5,5").unwrap();
+        assert_eq!(assign_fields(&notes), Err(AssignError::Impossible));
+    }
+}
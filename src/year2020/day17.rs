@@ -0,0 +1,199 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::life::{self, Cell, Rule};
+use crate::parse::{Input, ParseError};
+
+
+/// Cube of a pocket dimension with D coordinates.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct PointN<const D: usize>([i32; D]);
+
+
+type Pocket<const D: usize> = HashSet<PointN<D>>;
+
+
+impl<const D: usize> PointN<D> {
+    /// Embeds a point with fewer coordinates, the additional ones are 0.
+    fn embed<const E: usize>(p: &PointN<E>) -> PointN<D> {
+        assert!(E <= D, "Cannot embed {} dimensions into {}", E, D);
+        let mut coords = [0; D];
+        coords[..E].copy_from_slice(&p.0);
+        PointN(coords)
+    }
+
+    /// Representative of the points which only differ by mirrored or swapped coordinates
+    /// after the first two: those coordinates as absolute values in ascending order.
+    fn canonical(&self) -> PointN<D> {
+        let mut coords = self.0;
+        for c in coords[2..].iter_mut() {
+            *c = c.abs();
+        }
+        coords[2..].sort_unstable();
+        PointN(coords)
+    }
+
+    /// Number of points with the same representative as this canonical point,
+    /// the distinct permutations of the higher coordinates times the sign choices.
+    fn orbit_size(&self) -> usize {
+        let higher = &self.0[2..];
+        let factorial = |n: usize| (1..=n).product::<usize>();
+        let permutations = higher.chunk_by(|a, b| a == b).fold(factorial(higher.len()), |n, run| n / factorial(run.len()));
+        permutations << higher.iter().filter(|&&c| c != 0).count()
+    }
+}
+
+
+impl<const D: usize> Cell for PointN<D> {
+    /// The 3^D - 1 surrounding cubes, offsets are enumerated as numbers in base 3.
+    fn neighbours(&self) -> Vec<PointN<D>> {
+        let centre = (3usize.pow(D as u32) - 1) / 2;
+        (0..3usize.pow(D as u32)).filter(|&n| n != centre).map(|mut n| {
+            let mut coords = self.0;
+            for c in coords.iter_mut() {
+                *c += (n % 3) as i32 - 1;
+                n /= 3;
+            }
+            PointN(coords)
+        }).collect()
+    }
+}
+
+
+#[aoc_generator(day17)]
+fn parse_init(input: &str) -> Result<Pocket<2>, ParseError> {
+    let src = Input::new(17, input);
+    let mut hs = HashSet::new();
+    for (i, l) in input.lines().enumerate() {
+        for (j, (k, c)) in l.char_indices().enumerate() {
+            match c {
+                '#' => { hs.insert(PointN([j as i32, i as i32])); },
+                '.' => {},
+                _ => { return Err(src.error(&l[k..k + c.len_utf8()], "'#' or '.'")); },
+            }
+        }
+    }
+    Ok(hs)
+}
+
+
+/// Cycles searched for a repetition of the pocket, to keep the number of stored pockets bounded.
+const HISTORY_LIMIT: usize = 1000;
+
+
+/// Runs the boot process with a rule in D dimensions, starting from the initial slice,
+/// and returns the number of active cubes after the given number of cycles.
+pub fn boot<const D: usize>(init: &Pocket<2>, rule: Rule, cycles: usize) -> usize {
+    let poc: Pocket<D> = init.iter().map(PointN::embed).collect();
+    life::nth(poc, cycles, HISTORY_LIMIT, |p| life::step(p, rule)).len()
+}
+
+
+/// Same as boot, but makes use of the initial slice having all higher coordinates 0.
+/// The pocket then stays symmetric under mirroring and swapping the higher coordinates,
+/// so it only stores canonical cubes and counts neighbours weighted by orbit size.
+pub fn boot_symmetric<const D: usize>(init: &Pocket<2>, rule: Rule, cycles: usize) -> usize {
+    assert!(!rule.next(false, 0), "Rule {} activates cubes without neighbours", rule);
+    let poc: Pocket<D> = init.iter().map(PointN::embed).collect();
+    let poc = life::nth(poc, cycles, HISTORY_LIMIT, |p| step_symmetric(p, rule));
+    poc.iter().map(|p| p.orbit_size()).sum()
+}
+
+
+/// One cycle on a pocket of canonical cubes.
+fn step_symmetric<const D: usize>(poc: &Pocket<D>, rule: Rule) -> Pocket<D> {
+    // summed over the orbit of the target, so divided by its size below
+    let mut counts: HashMap<PointN<D>, usize> = HashMap::new();
+    for active in poc.iter() {
+        let weight = active.orbit_size();
+        for n in active.neighbours() {
+            *counts.entry(n.canonical()).or_insert(0) += weight;
+        }
+    }
+    let survivors = poc.iter().filter(|p| rule.next(true, counts.get(p).map_or(0, |n| n / p.orbit_size())));
+    let born = counts.iter().filter(|(p, n)| !poc.contains(p) && rule.next(false, *n / p.orbit_size()));
+    survivors.cloned().chain(born.map(|(p, _)| *p)).collect()
+}
+
+
+#[aoc(day17, part1)]
+pub fn solve_part1(input: &Pocket<2>) -> usize {
+    boot_symmetric::<3>(input, Rule::LIFE, 6)
+}
+
+
+#[aoc(day17, part2)]
+pub fn solve_part2(input: &Pocket<2>) -> usize {
+    boot_symmetric::<4>(input, Rule::LIFE, 6)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = ".#.\n..#\n###";
+
+    #[test]
+    fn test_parser() {
+        let expect: Pocket<2> = [[1, 0], [2, 1], [0, 2], [1, 2], [2, 2]].iter().map(|&c| PointN(c)).collect();
+        assert_eq!(parse_init(TEST_INPUT).unwrap(), expect);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        assert_eq!(PointN([0]).neighbours(), vec![PointN([-1]), PointN([1])]);
+        let n = PointN([1, 2, 3]).neighbours();
+        assert_eq!(n.len(), 26);
+        assert!(n.iter().all(|p| p != &PointN([1, 2, 3]) && p.0.iter().zip(&[1, 2, 3]).all(|(a, b)| (a - b).abs() <= 1)));
+        assert_eq!(PointN::<4>::embed(&PointN([5, 6])), PointN([5, 6, 0, 0]));
+    }
+
+    #[test]
+    fn test_part1_solver() {
+        assert_eq!(solve_part1(&parse_init(TEST_INPUT).unwrap()), 112);
+    }
+
+    #[test]
+    fn test_part2_solver() {
+        assert_eq!(solve_part2(&parse_init(TEST_INPUT).unwrap()), 848);
+    }
+
+    #[test]
+    fn test_orbit_size() {
+        assert_eq!(PointN([3, 4]).orbit_size(), 1);
+        assert_eq!(PointN([3, 4, 0]).orbit_size(), 1);
+        assert_eq!(PointN([3, 4, 2]).orbit_size(), 2);
+        assert_eq!(PointN([0, 0, 0, 1]).orbit_size(), 4);
+        assert_eq!(PointN([0, 0, 1, 1]).orbit_size(), 4);
+        assert_eq!(PointN([0, 0, 1, 2]).orbit_size(), 8);
+        assert_eq!(PointN([0, 0, 0, 1, 1, 2]).orbit_size(), 12 * 8);
+        assert_eq!(PointN([7, 8, 2, -1, 0]).canonical(), PointN([7, 8, 0, 1, 2]));
+    }
+
+    #[test]
+    fn test_higher_dimensions() {
+        let init = parse_init(TEST_INPUT).unwrap();
+        assert_eq!(boot::<2>(&init, Rule::LIFE, 1), 5);
+        // nothing is ever born in this rule, so the pocket is empty after one cycle
+        assert_eq!(boot::<3>(&init, "B9/S".parse().unwrap(), 1_000_000_000), 0);
+        assert_eq!(boot_symmetric::<5>(&init, Rule::LIFE, 6), 5760);
+        assert_eq!(boot_symmetric::<6>(&init, Rule::LIFE, 6), 35936);
+    }
+
+    #[test]
+    fn test_symmetric_matches_naive() {
+        let init = parse_init(TEST_INPUT).unwrap();
+        let wide = parse_init("##..#\n.#.##\n#...#").unwrap();
+        for cycles in 0..=4 {
+            assert_eq!(boot_symmetric::<3>(&init, Rule::LIFE, cycles), boot::<3>(&init, Rule::LIFE, cycles));
+            assert_eq!(boot_symmetric::<4>(&init, Rule::LIFE, cycles), boot::<4>(&init, Rule::LIFE, cycles));
+            assert_eq!(boot_symmetric::<5>(&init, Rule::LIFE, cycles), boot::<5>(&init, Rule::LIFE, cycles));
+            assert_eq!(boot_symmetric::<3>(&wide, Rule::LIFE, cycles), boot::<3>(&wide, Rule::LIFE, cycles));
+            assert_eq!(boot_symmetric::<4>(&wide, Rule::LIFE, cycles), boot::<4>(&wide, Rule::LIFE, cycles));
+        }
+        let rule = "B36/S023".parse().unwrap();
+        for cycles in 0..=3 {
+            assert_eq!(boot_symmetric::<4>(&wide, rule, cycles), boot::<4>(&wide, rule, cycles));
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::slice::Iter;
+use std::iter::Peekable;
+
+use crate::parse::{Input, ParseError};
+
+
+#[derive(Debug, PartialEq)]
+pub enum Expr {
+    Add(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Val(u64),
+}
+
+
+use Expr::*;
+
+
+impl Expr {
+    fn eval(&self) -> u64 {
+        match self {
+            Add(a, b) => a.eval() + b.eval(),
+            Mul(a, b) => a.eval() * b.eval(),
+            Val(a) => *a,
+        }
+    }
+}
+
+
+#[derive(Debug)]
+enum Token {
+    Plus,
+    Times,
+    POpen,
+    PClose,
+    Digit(u64),
+    End,
+}
+
+
+use Token::*;
+
+
+/// Byte range in the expression and what was expected there.
+#[derive(Debug, PartialEq)]
+pub struct ExprError {
+    pos: usize,
+    len: usize,
+    expected: &'static str,
+}
+
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected {} at position {}", self.expected, self.pos)
+    }
+}
+
+
+impl Error for ExprError {}
+
+
+/// Splits the expression into tokens with their position, followed by an End token.
+fn tokenize(s: &str) -> Result<Vec<(usize, Token)>, ExprError> {
+    let mut tokens = s.char_indices().filter(|(_, c)| !c.is_whitespace()).map(|(i, c)| match c {
+        '0'..='9' => Ok((i, Digit(c.to_digit(10).unwrap() as u64))),
+        '(' => Ok((i, POpen)),
+        ')' => Ok((i, PClose)),
+        '+' => Ok((i, Plus)),
+        '*' => Ok((i, Times)),
+        _ => Err(ExprError { pos: i, len: c.len_utf8(), expected: "a digit, operator or paren" }),
+    }).collect::<Result<Vec<_>, _>>()?;
+    tokens.push((s.len(), End));
+    Ok(tokens)
+}
+
+
+/// Binding power of the binary operators, operators with a higher value bind tighter.
+#[derive(Debug, Clone, Copy)]
+pub struct Precedence {
+    plus: u8,
+    times: u8,
+}
+
+
+/// Both operators bind equally strong, expressions are evaluated left to right.
+pub const LEFT_TO_RIGHT: Precedence = Precedence { plus: 1, times: 1 };
+/// Addition binds tighter than multiplication.
+pub const ADD_FIRST: Precedence = Precedence { plus: 2, times: 1 };
+/// Usual arithmetic rules, multiplication binds tighter than addition.
+pub const ARITHMETIC: Precedence = Precedence { plus: 1, times: 2 };
+
+
+impl Precedence {
+    fn of(&self, t: &Token) -> Option<u8> {
+        match t {
+            Plus => Some(self.plus),
+            Times => Some(self.times),
+            _ => None,
+        }
+    }
+}
+
+
+fn expected(e: &'static str, (pos, t): &(usize, Token)) -> Result<Expr, ExprError> {
+    let len = match t {
+        End => 0,
+        _ => 1,
+    };
+    Err(ExprError { pos: *pos, len, expected: e })
+}
+
+
+fn consume_op(tokens: &mut Peekable<Iter<(usize, Token)>>, prec: Precedence) -> Result<Expr, ExprError> {
+    // tokens always end with End, which is never consumed
+    match tokens.peek().unwrap() {
+        (_, Digit(d)) => { tokens.next(); Ok(Val(*d)) },
+        (_, POpen) => {
+            tokens.next();
+            let expr = parse_expr(tokens, prec, 0)?;
+            match tokens.peek().unwrap() {
+                (_, PClose) => { tokens.next(); Ok(expr) },
+                u => expected("closing paren", u),
+            }
+        },
+        u => expected("operand", u),
+    }
+}
+
+
+/// Precedence climbing parser, consumes operators binding at least as strong as min_bp.
+/// Stops before a closing paren or at the end of the tokens.
+fn parse_expr(tokens: &mut Peekable<Iter<(usize, Token)>>, prec: Precedence, min_bp: u8) -> Result<Expr, ExprError> {
+    let mut left = consume_op(tokens, prec)?;
+    while let Some(&tok) = tokens.peek() {
+        let t = &tok.1;
+        let bp = match t {
+            PClose | End => break,
+            _ => match prec.of(t) {
+                Some(bp) => bp,
+                None => return expected("operator", tok),
+            },
+        };
+        if bp < min_bp {
+            break;
+        }
+        tokens.next();
+        // all operators are left associative, so the right side has to bind tighter
+        let right = parse_expr(tokens, prec, bp+1)?;
+        left = match t {
+            Plus => Add(Box::new(left), Box::new(right)),
+            _ => Mul(Box::new(left), Box::new(right)),
+        };
+    }
+    Ok(left)
+}
+
+
+impl Expr {
+    /// Parses an expression with the given operator precedence.
+    pub fn parse_with(s: &str, prec: Precedence) -> Result<Expr, ExprError> {
+        let tokens = tokenize(s)?;
+        let mut tokens = tokens.iter().peekable();
+        let expr = parse_expr(&mut tokens, prec, 0)?;
+        // a closing paren without an opening one stops parse_expr early
+        match tokens.peek().unwrap() {
+            (_, End) => Ok(expr),
+            u => expected("end of expression", u),
+        }
+    }
+}
+
+
+impl FromStr for Expr {
+    type Err = ExprError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Expr::parse_with(s, LEFT_TO_RIGHT)
+    }
+}
+
+
+fn parse_lines(input: &str, prec: Precedence) -> Result<Vec<Expr>, ParseError> {
+    let src = Input::new(18, input);
+    input.lines().map(|l| {
+        Expr::parse_with(l, prec).map_err(|e| src.error(&l[e.pos..e.pos + e.len], e.expected))
+    }).collect()
+}
+
+
+#[aoc_generator(day18, part1)]
+fn parse_exprs(input: &str) -> Result<Vec<Expr>, ParseError> {
+    parse_lines(input, LEFT_TO_RIGHT)
+}
+
+
+#[aoc_generator(day18, part2)]
+fn parse_exprs_add_first(input: &str) -> Result<Vec<Expr>, ParseError> {
+    parse_lines(input, ADD_FIRST)
+}
+
+
+#[aoc(day18, part1)]
+pub fn solve_part1(input: &[Expr]) -> u64 {
+    input.iter().map(|e| e.eval()).sum()
+}
+
+
+#[aoc(day18, part2)]
+pub fn solve_part2(input: &[Expr]) -> u64 {
+    input.iter().map(|e| e.eval()).sum()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        let expect1 = Add(
+            Box::new(Mul(
+                Box::new(Val(2)),
+                Box::new(Val(3))
+            )),
+            Box::new(Mul(
+                Box::new(Val(4)),
+                Box::new(Val(5))
+            ))
+        );
+        assert_eq!("2 * 3 + (4 * 5)".parse::<Expr>().unwrap(), expect1);
+        let expect2 = Mul(
+            Box::new(Add(
+                Box::new(Val(9)),
+                Box::new(Val(3)),
+            )),
+            Box::new(Val(4))
+        );
+        assert_eq!("9 + 3 * 4".parse::<Expr>().unwrap(), expect2);
+        assert_eq!("9 + 3 * 4)".parse::<Expr>(), Err(ExprError { pos: 9, len: 1, expected: "end of expression" }));
+        assert_eq!("1 + 2) * 3".parse::<Expr>().unwrap_err().to_string(), "expected end of expression at position 5");
+        assert_eq!(parse_exprs("1\n2").unwrap(), vec![Val(1), Val(2)]);
+    }
+
+    #[test]
+    fn test_eval() {
+        assert_eq!("2 * 3 + (4 * 5)".parse::<Expr>().unwrap().eval(), 26);
+        assert_eq!("5 + (8 * 3 + 9 + 3 * 4 * 3)".parse::<Expr>().unwrap().eval(), 437);
+    }
+
+    #[test]
+    fn test_precedence() {
+        let eval = |s, prec| Expr::parse_with(s, prec).unwrap().eval();
+        assert_eq!(eval("1 + 2 * 3 + 4 * 5 + 6", ADD_FIRST), 231);
+        assert_eq!(eval("2 * 3 + (4 * 5)", ADD_FIRST), 46);
+        assert_eq!(eval("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2", ADD_FIRST), 23340);
+        assert_eq!(eval("1 + 2 * 3 + 4 * 5 + 6", ARITHMETIC), 33);
+        assert_eq!(eval("2 * (3 + 4) * 5", ARITHMETIC), 70);
+        assert!(Expr::parse_with("(1 + 2", ADD_FIRST).is_err());
+        assert!(Expr::parse_with("1 + * 2", ADD_FIRST).is_err());
+    }
+
+    #[test]
+    fn test_parser_errors() {
+        let err = parse_exprs("1 + 2\n(1 + 2").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (2, 7, "closing paren"));
+        let err = parse_exprs("1 + * 2").unwrap_err();
+        assert_eq!((err.column, err.text.as_str(), err.expected.as_str()), (5, "*", "operand"));
+        let err = parse_exprs_add_first("1 - 2").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (3, "-"));
+        assert_eq!("2 2".parse::<Expr>().unwrap_err().to_string(), "expected operator at position 2");
+    }
+
+    #[test]
+    fn test_part1_solver() {
+        assert_eq!(solve_part1(&parse_exprs("2 * 3 + (4 * 5)\n5 + (8 * 3 + 9 + 3 * 4 * 3)").unwrap()), 26+437);
+    }
+
+    #[test]
+    fn test_part2_solver() {
+        let input = "5 + (8 * 3 + 9 + 3 * 4 * 3)\n5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))";
+        assert_eq!(solve_part2(&parse_exprs_add_first(input).unwrap()), 1445+669060);
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::parse::{Input, ParseError};
+
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Rule {
+    Char(char),
+    Seq(Vec<u32>),
+    Any(Vec<Rule>),
+}
+
+
+use Rule::*;
+
+
+/// Rules by id, rules reference each other by id so loops are allowed.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Grammar(HashMap<u32, Rule>);
+
+
+impl Grammar {
+    fn rule(&self, id: u32) -> &Rule {
+        self.0.get(&id).unwrap_or_else(|| panic!("Missing rule {}", id))
+    }
+
+    fn set_rule(&mut self, id: u32, rule: Rule) {
+        self.0.insert(id, rule);
+    }
+
+    /// Returns the set of positions where a match of rule starting at pos can end.
+    /// Ambiguous alternatives reaching the same position are only followed once.
+    /// Rules have to consume at least one char before referencing themselves,
+    /// parse_input rejects left recursive rules.
+    fn ends(&self, rule: &Rule, s: &str, pos: usize) -> BTreeSet<usize> {
+        match rule {
+            Char(c) => s[pos..].starts_with(*c).then(|| pos + c.len_utf8()).into_iter().collect(),
+            Seq(ids) => ids.iter().fold(BTreeSet::from([pos]), |ends, &id| {
+                ends.into_iter().flat_map(|p| self.ends(self.rule(id), s, p)).collect()
+            }),
+            Any(v) => v.iter().flat_map(|r| self.ends(r, s, pos)).collect(),
+        }
+    }
+
+    /// Checks if rule 0 matches the whole string.
+    fn matches(&self, s: &str) -> bool {
+        self.ends(self.rule(0), s, 0).contains(&s.len())
+    }
+
+    /// Ids of the rules that can match the empty string.
+    fn empty_rules(&self) -> HashSet<u32> {
+        fn can_be_empty(rule: &Rule, empty: &HashSet<u32>) -> bool {
+            match rule {
+                Char(_) => false,
+                Seq(ids) => ids.iter().all(|id| empty.contains(id)),
+                Any(v) => v.iter().any(|r| can_be_empty(r, empty)),
+            }
+        }
+        let mut empty = HashSet::new();
+        loop {
+            let found: Vec<u32> = self.0.iter()
+                .filter(|(id, rule)| !empty.contains(*id) && can_be_empty(rule, &empty))
+                .map(|(&id, _)| id).collect();
+            if found.is_empty() {
+                return empty;
+            }
+            empty.extend(found);
+        }
+    }
+
+    /// Ids a rule refers to before matching a char.
+    fn leading(rule: &Rule, empty: &HashSet<u32>, refs: &mut Vec<u32>) {
+        match rule {
+            Char(_) => {},
+            Seq(ids) => {
+                for &id in ids {
+                    refs.push(id);
+                    if !empty.contains(&id) {
+                        break;
+                    }
+                }
+            },
+            Any(v) => v.iter().for_each(|r| Grammar::leading(r, empty, refs)),
+        }
+    }
+
+    /// A rule that can refer to itself before matching a char, starting the search at id.
+    /// Rules are marked false while they are searched and true once they are done.
+    fn left_recursion(&self, id: u32, empty: &HashSet<u32>, state: &mut HashMap<u32, bool>) -> Option<u32> {
+        match state.get(&id) {
+            Some(false) => return Some(id),
+            Some(true) => return None,
+            None => {},
+        }
+        state.insert(id, false);
+        let mut refs = Vec::new();
+        Grammar::leading(self.rule(id), empty, &mut refs);
+        for r in refs {
+            if let Some(found) = self.left_recursion(r, empty, state) {
+                return Some(found);
+            }
+        }
+        state.insert(id, true);
+        None
+    }
+}
+
+
+/// Parses the right side of a rule, returns the offending part of it on error.
+fn parse_rule(rule: &str) -> Result<Rule, &str> {
+    if rule.contains('|') {
+        Ok(Any(rule.split('|').map(|r| parse_rule(r.trim())).collect::<Result<_, _>>()?))
+    } else if rule.len() > 1 && rule.starts_with('"') && rule.ends_with('"') {
+        let inner = &rule[1..rule.len() - 1];
+        let mut chars = inner.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(Char(c)),
+            _ => Err(rule),
+        }
+    } else {
+        Ok(Seq(rule.split_whitespace().map(|id| id.parse().map_err(|_| id)).collect::<Result<_, _>>()?))
+    }
+}
+
+
+#[aoc_generator(day19)]
+fn parse_input(input: &str) -> Result<(Grammar, Vec<String>), ParseError> {
+    let src = Input::new(19, input);
+    let mut parts = input.split("\n\n");
+    let rule_part = parts.next().unwrap();
+    let rules = rule_part
+        .lines().map(|l| {
+            let mut sp = l.split(':');
+            let id = src.parse(sp.next().unwrap(), "a rule id")?;
+            let rule = src.expect(sp.next(), l, "<id>: <rule>")?.trim();
+            Ok((id, rule, parse_rule(rule).map_err(|e| src.error(e, "a rule id or a quoted char"))?))
+        }).collect::<Result<Vec<_>, ParseError>>()?;
+    // matching would run into a missing rule
+    let ids: HashSet<u32> = rules.iter().map(|r| r.0).collect();
+    if !ids.contains(&0) {
+        return Err(src.error(&rule_part[rule_part.len()..], "rule 0"));
+    }
+    for (_, rule, _) in rules.iter() {
+        let refs = rule.split(|c: char| c == '|' || c.is_whitespace()).filter(|r| !r.is_empty() && !r.starts_with('"'));
+        for r in refs {
+            if !ids.contains(&r.parse().unwrap()) {
+                return Err(src.error(r, "the id of a defined rule"));
+            }
+        }
+    }
+    let messages = src.expect(parts.next(), rule_part, "messages after an empty line")?;
+    let grammar = Grammar(rules.iter().map(|(id, _, rule)| (*id, rule.clone())).collect());
+    // ends would recurse without bound on a left recursive rule
+    let empty = grammar.empty_rules();
+    let mut state = HashMap::new();
+    for &(id, _, _) in rules.iter() {
+        if let Some(r) = grammar.left_recursion(id, &empty, &mut state) {
+            let text = rules.iter().find(|rule| rule.0 == r).unwrap().1;
+            return Err(src.error(text, "a rule matching a char before it refers to itself"));
+        }
+    }
+    Ok((grammar, messages.lines().map(|s| s.to_string()).collect()))
+}
+
+
+#[aoc(day19, part1)]
+pub fn solve_part1(input: &(Grammar, Vec<String>)) -> usize {
+    input.1.iter().filter(|s| input.0.matches(s)).count()
+}
+
+
+#[aoc(day19, part2)]
+pub fn solve_part2(input: &(Grammar, Vec<String>)) -> Option<usize> {
+    let mut grammar = input.0.clone();
+    // the replaced rules refer to 42 and 31
+    if !(grammar.0.contains_key(&42) && grammar.0.contains_key(&31)) {
+        return None;
+    }
+    grammar.set_rule(8, parse_rule("42 | 42 8").unwrap());
+    grammar.set_rule(11, parse_rule("42 31 | 42 11 31").unwrap());
+    Some(input.1.iter().filter(|s| grammar.matches(s)).count())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: \"a\"
+5: \"b\"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb";
+
+    const TEST_INPUT2: &str = "42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: \"a\"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: \"b\"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1
+
+abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba";
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(parse_rule("\"a\"").unwrap(), Char('a'));
+        assert_eq!(parse_rule("1 2").unwrap(), Seq(vec![1, 2]));
+        assert_eq!(parse_rule("1 2 | 3").unwrap(), Any(vec![Seq(vec![1, 2]), Seq(vec![3])]));
+        assert_eq!(parse_input("0: 1 1\n1: \"a\"\n\naa").unwrap().0.rule(0), &Seq(vec![1, 1]));
+    }
+
+    #[test]
+    fn test_parser_errors() {
+        let err = parse_input("0: 1 2\n1: \"a\"\n\nab").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 6, "2"));
+        let err = parse_input("0: 1 | 1 3\n1: \"a\"\n\nab").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 10, "3"));
+        let err = parse_input("1: \"a\"\n\na").unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (1, "rule 0"));
+    }
+
+    #[test]
+    fn test_left_recursion() {
+        let err = parse_input("0: 8\n8: 8 42 | 42\n42: \"a\"\n\na").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 4, "8 42 | 42"));
+        assert_eq!(err.expected, "a rule matching a char before it refers to itself");
+        // through another rule
+        let err = parse_input("0: 1 2\n1: 2 | 0 2\n2: \"a\"\n\na").unwrap_err();
+        assert_eq!(err.line, 1);
+        // after a rule matching the empty string
+        let err = parse_input("0: 1 0 | 2\n1: \n2: \"a\"\n\na").unwrap_err();
+        assert_eq!(err.line, 1);
+        // right recursion is fine
+        let grammar = parse_input("0: 2 0 | 2\n2: \"a\"\n\na").unwrap().0;
+        assert!(grammar.matches("aaa"));
+    }
+
+    #[test]
+    fn test_chars_clone() {
+        let mut c1 = "abc".chars();
+        let mut c2 = c1.clone();
+        assert_eq!(c1.next(), c2.next());
+        assert_eq!(c1.next(), c2.next());
+        assert_eq!(c1.next(), c2.next());
+        assert_eq!(c1.next(), None);
+    }
+
+    #[test]
+    fn test_matches() {
+        let char_rule = parse_input("0: \"a\"\n\na").unwrap().0;
+        assert!(char_rule.matches("a"));
+        assert!(!char_rule.matches("ab"));
+        assert!(!char_rule.matches("b"));
+        let seq_rule = parse_input("0: 1 2 1\n1: \"a\"\n2: \"b\"\n\na").unwrap().0;
+        assert!(seq_rule.matches("aba"));
+        assert!(!seq_rule.matches("baa"));
+        let any_rule = parse_input("0: 1 | 2\n1: \"a\"\n2: \"b\"\n\na").unwrap().0;
+        assert!(any_rule.matches("a"));
+        assert!(any_rule.matches("b"));
+        assert!(!any_rule.matches("aa"));
+        let rule = parse_input(TEST_INPUT).unwrap().0;
+        assert!(rule.matches("ababbb"));
+        assert!(!rule.matches("bababa"));
+    }
+
+    #[test]
+    fn test_ends() {
+        let grammar = parse_input("0: 1 | 1 0\n1: \"a\"\n\na").unwrap().0;
+        assert_eq!(grammar.ends(grammar.rule(0), "aaa", 0), BTreeSet::from([1, 2, 3]));
+        assert_eq!(grammar.ends(grammar.rule(0), "aab", 1), BTreeSet::from([2]));
+        assert_eq!(grammar.ends(grammar.rule(0), "b", 0), BTreeSet::new());
+        // both splits of "aaa" end at the same position
+        let grammar = parse_input("0: 1 1\n1: 2 | 2 2\n2: \"a\"\n\na").unwrap().0;
+        assert_eq!(grammar.ends(grammar.rule(0), "aaaa", 0), BTreeSet::from([2, 3, 4]));
+    }
+
+    #[test]
+    fn test_matches_backtracking() {
+        // the longer alternative matches a prefix, but only the shorter one leaves "ab" for rule 2
+        let grammar = parse_input("0: 1 2\n1: 3 3 | 3\n2: 3 4\n3: \"a\"\n4: \"b\"\n\na").unwrap().0;
+        assert!(grammar.matches("aab"));
+        assert!(grammar.matches("aaab"));
+        assert!(!grammar.matches("aaaab"));
+        // the later element of the sequence fails unless an earlier alternative is retried
+        let grammar = parse_input("0: 1 1 2\n1: 2 2 | 2\n2: \"a\"\n\na").unwrap().0;
+        assert!(grammar.matches("aaa"));
+        assert!(grammar.matches("aaaaa"));
+        assert!(!grammar.matches("aa"));
+        assert!(!grammar.matches("aaaaaa"));
+    }
+
+    #[test]
+    fn test_matches_ambiguous() {
+        // exponentially many parse trees, only distinct end positions are tracked
+        let grammar = parse_input("0: 1 1 1 1 1 1 1 1 1 1 1 1 1 1 1 1\n1: 2 | 2 2 | 2 2 2\n2: \"a\"\n\na").unwrap().0;
+        assert!(grammar.matches(&"a".repeat(16)));
+        assert!(grammar.matches(&"a".repeat(48)));
+        assert!(!grammar.matches(&"a".repeat(49)));
+    }
+
+    #[test]
+    fn test_part1_solver() {
+        assert_eq!(solve_part1(&parse_input(TEST_INPUT).unwrap()), 2);
+        assert_eq!(solve_part1(&parse_input(TEST_INPUT2).unwrap()), 3);
+    }
+
+    #[test]
+    fn test_part2_solver() {
+        assert_eq!(solve_part2(&parse_input(TEST_INPUT2).unwrap()), Some(12));
+    }
+}
@@ -1,5 +1,7 @@
 use regex::Regex;
 
+use crate::parse::{Input, ParseError};
+
 pub struct PassEntry {
     low: usize,
     high: usize,
@@ -9,11 +11,11 @@ pub struct PassEntry {
 
 impl PassEntry {
     pub fn new(low: usize, high: usize, letter: char, password: String) -> PassEntry {
-        PassEntry{ low: low, high: high, letter: letter, password: password }
+        PassEntry{ low, high, letter, password }
     }
 
     pub fn is_valid(&self) -> bool {
-        let num = self.password.chars().into_iter().filter(|c| *c == self.letter).count();
+        let num = self.password.chars().filter(|c| *c == self.letter).count();
         self.low <= num && num <= self.high
     }
 
@@ -24,14 +26,15 @@ impl PassEntry {
 }
 
 #[aoc_generator(day2)]
-fn parse_entries<'a>(input: &str) -> Vec<PassEntry> {
+fn parse_entries(input: &str) -> Result<Vec<PassEntry>, ParseError> {
+    let src = Input::new(2, input);
     let re = Regex::new(r"^(\d+)-(\d+) (.): (.+)").unwrap();
     input.lines().map(|l| {
-        let cap = re.captures(l).unwrap();
-        PassEntry::new(cap.get(1).unwrap().as_str().parse().unwrap(),
-                       cap.get(2).unwrap().as_str().parse().unwrap(),
-                       cap.get(3).unwrap().as_str().parse().unwrap(),
-                       cap.get(4).unwrap().as_str().to_string())
+        let cap = re.captures(l).ok_or_else(|| src.error(l, "<low>-<high> <letter>: <password>"))?;
+        Ok(PassEntry::new(src.parse(cap.get(1).unwrap().as_str(), "a number")?,
+                          src.parse(cap.get(2).unwrap().as_str(), "a number")?,
+                          src.parse(cap.get(3).unwrap().as_str(), "a letter")?,
+                          cap.get(4).unwrap().as_str().to_string()))
     }).collect()
 }
 
@@ -0,0 +1,371 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::grid::{Grid, Orientation};
+use crate::parse::{Input, ParseError};
+
+
+const SEA_MONSTER: &str = "                  #
+#    ##    ##    ###
+ #  #  #  #  #  #   ";
+
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Tile {
+    id: u64,
+    image: Grid<bool>,
+}
+
+
+/// Reads the borders of a tile in the order top, right, bottom, left.
+/// Each border is read clockwise, so two matching borders of neighbouring tiles are reversed.
+fn borders(g: &Grid<bool>) -> [Vec<bool>; 4] {
+    let (w, h) = (g.width(), g.height());
+    [g.row(0).to_vec(),
+     g.column(w - 1).cloned().collect(),
+     g.row(h - 1).iter().rev().cloned().collect(),
+     g.column(0).rev().cloned().collect()]
+}
+
+
+/// Border without regard to direction, the lexically smaller one of both readings.
+fn canonical(border: &[bool]) -> Vec<bool> {
+    let rev: Vec<bool> = border.iter().rev().cloned().collect();
+    if rev.as_slice() < border { rev } else { border.to_vec() }
+}
+
+
+#[aoc_generator(day20)]
+fn parse_tiles(input: &str) -> Result<Vec<Tile>, ParseError> {
+    let src = Input::new(20, input);
+    input.split("\n\n").map(|t| {
+        let (header, image) = t.split_once('\n').unwrap_or((t, ""));
+        let id = header.strip_prefix("Tile ").and_then(|h| h.strip_suffix(':'))
+            .ok_or_else(|| src.error(header, "Tile <id>:"))?;
+        let id = src.parse(id, "a tile id")?;
+        let image = Grid::parse(&src, image, "'#' or '.'", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        // a border needs at least two pixels
+        if image.width() < 2 || image.height() < 2 {
+            return Err(src.error(&t[t.len()..], "a tile of at least 2x2 pixels"));
+        }
+        Ok(Tile{ id, image })
+    }).collect()
+}
+
+
+/// Counts the tiles each border appears in.
+fn border_counts(tiles: &[Tile]) -> HashMap<Vec<bool>, usize> {
+    let mut counts = HashMap::new();
+    for t in tiles.iter() {
+        for b in borders(&t.image).iter() {
+            *counts.entry(canonical(b)).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+
+#[aoc(day20, part1)]
+pub fn solve_part1(input: &[Tile]) -> u64 {
+    let counts = border_counts(input);
+    // corner tiles have two borders without a match
+    input.iter()
+        .filter(|t| borders(&t.image).iter().filter(|b| counts[&canonical(b)] == 1).count() == 2)
+        .map(|t| t.id)
+        .product()
+}
+
+
+/// Placement of oriented tiles, row by row.
+struct Assembly<'a> {
+    size: usize,
+    placed: Vec<(usize, &'a Grid<bool>)>,
+}
+
+
+impl<'a> Assembly<'a> {
+    /// Checks if the tile can be placed at the next free position.
+    fn fits(&self, g: &Grid<bool>) -> bool {
+        let pos = self.placed.len();
+        let left_ok = pos.is_multiple_of(self.size) || {
+            let left = self.placed[pos - 1].1;
+            left.column(left.width() - 1).eq(g.column(0))
+        };
+        let top_ok = pos < self.size || {
+            let top = self.placed[pos - self.size].1;
+            top.row(top.height() - 1) == g.row(0)
+        };
+        left_ok && top_ok
+    }
+
+    /// Backtracking search placing all remaining tiles.
+    fn place(&mut self, oriented: &'a [Vec<Grid<bool>>], used: &mut [bool]) -> bool {
+        if self.placed.len() == oriented.len() {
+            return true;
+        }
+        for i in 0..oriented.len() {
+            if used[i] {
+                continue;
+            }
+            for g in oriented[i].iter() {
+                if self.fits(g) {
+                    used[i] = true;
+                    self.placed.push((i, g));
+                    if self.place(oriented, used) {
+                        return true;
+                    }
+                    self.placed.pop();
+                    used[i] = false;
+                }
+            }
+        }
+        false
+    }
+}
+
+
+/// Arranges the tiles into a square and joins their images without the borders.
+fn assemble(tiles: &[Tile]) -> Option<Grid<bool>> {
+    let size = (tiles.len() as f64).sqrt() as usize;
+    if size * size != tiles.len() {
+        return None;
+    }
+    let oriented: Vec<Vec<Grid<bool>>> = tiles.iter().map(|t| t.image.orientations().collect()).collect();
+    let mut asm = Assembly{ size, placed: Vec::new() };
+    if !asm.place(&oriented, &mut vec![false; tiles.len()]) {
+        return None;
+    }
+
+    let inner = asm.placed[0].1.width() - 2;
+    Some(Grid::from_fn(size * inner, size * inner, |x, y| {
+        let g = asm.placed[(y / inner) * size + x / inner].1;
+        g[(x % inner + 1, y % inner + 1)]
+    }))
+}
+
+
+fn parse_pattern(pattern: &str) -> Vec<(usize, usize)> {
+    pattern.lines().enumerate().flat_map(|(y, l)| {
+        l.chars().enumerate().filter(|&(_, c)| c == '#').map(move |(x, _)| (x, y))
+    }).collect()
+}
+
+
+/// Returns the cells covered by occurrences of the pattern in the image.
+fn find_pattern(image: &Grid<bool>, pattern: &[(usize, usize)]) -> HashSet<(usize, usize)> {
+    let pw = pattern.iter().map(|p| p.0).max().unwrap_or(0) + 1;
+    let ph = pattern.iter().map(|p| p.1).max().unwrap_or(0) + 1;
+    let mut covered = HashSet::new();
+    for y in 0..=image.height().saturating_sub(ph) {
+        for x in 0..=image.width().saturating_sub(pw) {
+            if pattern.iter().all(|&(i, j)| image.get(x + i, y + j) == Some(&true)) {
+                covered.extend(pattern.iter().map(|&(i, j)| (x + i, y + j)));
+            }
+        }
+    }
+    covered
+}
+
+
+#[aoc(day20, part2)]
+pub fn solve_part2(input: &[Tile]) -> Option<usize> {
+    let image = assemble(input)?;
+    let monster = parse_pattern(SEA_MONSTER);
+    let total = image.iter().filter(|&&b| b).count();
+    Orientation::all()
+        .map(|o| find_pattern(&image.oriented(o), &monster).len())
+        .find(|&n| n > 0)
+        .map(|n| total - n)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "Tile 2311:
+..##.#..#.
+##..#.....
+#...##..#.
+####.#...#
+##.##.###.
+##...#.###
+.#.#.#..##
+..#....#..
+###...#.#.
+..###..###
+
+Tile 1951:
+#.##...##.
+#.####...#
+.....#..##
+#...######
+.##.#....#
+.###.#####
+###.##.##.
+.###....#.
+..#.#..#.#
+#...##.#..
+
+Tile 1171:
+####...##.
+#..##.#..#
+##.#..#.#.
+.###.####.
+..###.####
+.##....##.
+.#...####.
+#.##.####.
+####..#...
+.....##...
+
+Tile 1427:
+###.##.#..
+.#..#.##..
+.#.##.#..#
+#.#.#.##.#
+....#...##
+...##..##.
+...#.#####
+.#.####.#.
+..#..###.#
+..##.#..#.
+
+Tile 1489:
+##.#.#....
+..##...#..
+.##..##...
+..#...#...
+#####...#.
+#..#.#.#.#
+...#.#.#..
+##.#...##.
+..##.##.##
+###.##.#..
+
+Tile 2473:
+#....####.
+#..#.##...
+#.##..#...
+######.#.#
+.#...#.#.#
+.#########
+.###.#..#.
+########.#
+##...##.#.
+..###.#.#.
+
+Tile 2971:
+..#.#....#
+#...###...
+#.#.###...
+##.##..#..
+.#####..##
+.#..####.#
+#..#.#..#.
+..####.###
+..#.#.###.
+...#.#.#.#
+
+Tile 2729:
+...#.#.#.#
+####.#....
+..#.#.....
+....#..#.#
+.##..##.#.
+.#.####...
+####.#.#..
+##.####...
+##..#.##..
+#.##...##.
+
+Tile 3079:
+#.#.#####.
+.#..######
+..#.......
+######....
+####.#..#.
+.#...#.##.
+#.#####.##
+..#.###...
+..#.......
+..#.###...";
+
+    /// Small linear congruential generator, to get the same pseudo random tiles in every run.
+    fn lcg(seed: &mut u64) -> u64 {
+        *seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        *seed >> 33
+    }
+
+    /// Cuts a random picture into size*size shuffled and randomly oriented tiles.
+    /// Returns the tiles and the picture without the tile borders.
+    fn random_tiles(size: usize, seed: u64) -> (Vec<Tile>, Grid<bool>) {
+        let mut seed = seed;
+        let full = Grid::from_fn(9 * size + 1, 9 * size + 1, |_, _| lcg(&mut seed) & 1 == 0);
+        let orientations: Vec<Orientation> = Orientation::all().collect();
+        let mut tiles: Vec<Tile> = (0..size * size).map(|i| {
+            let image = full.cropped(9 * (i % size), 9 * (i / size), 10, 10);
+            let o = orientations[lcg(&mut seed) as usize % 8];
+            Tile{ id: 1000 + i as u64, image: image.oriented(o) }
+        }).collect();
+        for i in (1..tiles.len()).rev() {
+            tiles.swap(i, lcg(&mut seed) as usize % (i + 1));
+        }
+        let inner = Grid::from_fn(8 * size, 8 * size, |x, y| full[(x + 1 + x / 8, y + 1 + y / 8)]);
+        (tiles, inner)
+    }
+
+    #[test]
+    fn test_parser() {
+        let tiles = parse_tiles("Tile 2311:\n#.\n.#\n\nTile 1951:\n..\n##").unwrap();
+        assert_eq!(tiles[0], Tile{ id: 2311, image: Grid::from_rows(vec![vec![true, false], vec![false, true]]) });
+        assert_eq!(tiles[1].id, 1951);
+        assert_eq!(tiles[1].image.map(|&b| if b { '#' } else { '.' }).to_string(), "..\n##");
+        assert!(parse_tiles("Tile 1:\n#.\n.").is_err());
+        let err = parse_tiles("Tile 1:").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (1, 8, "a non-empty row"));
+        let err = parse_tiles("Tile 1:\n#\n.").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (3, 2, "a tile of at least 2x2 pixels"));
+    }
+
+    #[test]
+    fn test_borders() {
+        let g = Grid::from_rows(vec![vec![true, false], vec![true, true]]);
+        assert_eq!(borders(&g), [vec![true, false], vec![false, true], vec![true, true], vec![true, true]]);
+        assert_eq!(canonical(&[true, false]), vec![false, true]);
+    }
+
+    #[test]
+    fn test_part1_solver() {
+        assert_eq!(solve_part1(&parse_tiles(TEST_INPUT).unwrap()), 20899048083289);
+        let (tiles, _) = random_tiles(3, 20);
+        // tiles in the corners of the picture are 1000, 1002, 1006 and 1008
+        assert_eq!(solve_part1(&tiles), 1000 * 1002 * 1006 * 1008);
+    }
+
+    #[test]
+    fn test_part2_solver() {
+        assert_eq!(solve_part2(&parse_tiles(TEST_INPUT).unwrap()), Some(273));
+    }
+
+    #[test]
+    fn test_assemble() {
+        let (tiles, inner) = random_tiles(4, 7);
+        let image = assemble(&tiles).unwrap();
+        assert!(inner.orientations().any(|g| g == image));
+        assert_eq!(assemble(&tiles[1..]), None);
+    }
+
+    #[test]
+    fn test_find_pattern() {
+        let monster = parse_pattern(SEA_MONSTER);
+        assert_eq!(monster.len(), 15);
+        let image = Grid::from_fn(24, 5, |x, y| x >= 2 && y >= 1 && monster.contains(&(x - 2, y - 1)));
+        assert_eq!(find_pattern(&image, &monster).len(), 15);
+        assert_eq!(find_pattern(&image.flipped(), &monster).len(), 0);
+        assert_eq!(find_pattern(&image.cropped(0, 0, 21, 5), &monster).len(), 0);
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+
+use crate::parse::{Input, ParseError};
+
+
+#[derive(Debug, PartialEq)]
+pub struct Food {
+    ingredients: HashSet<String>,
+    allergens: Vec<String>,
+}
+
+
+#[derive(Debug, PartialEq)]
+pub enum MappingError {
+    /// Some allergen is contained in no ingredient.
+    Impossible(String),
+    /// The allergens with more than one possible ingredient after elimination.
+    Ambiguous(Vec<String>),
+}
+
+
+impl fmt::Display for MappingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MappingError::Impossible(a) => write!(f, "no ingredient can contain {}", a),
+            MappingError::Ambiguous(v) => write!(f, "ambiguous ingredients for allergens: {}", v.join(", ")),
+        }
+    }
+}
+
+
+impl Error for MappingError {}
+
+
+#[aoc_generator(day21)]
+fn parse_foods(input: &str) -> Result<Vec<Food>, ParseError> {
+    let src = Input::new(21, input);
+    input.lines().map(|l| {
+        let mut parts = l.splitn(2, " (contains ");
+        let ingredients = parts.next().unwrap().split_whitespace().map(|s| s.to_string()).collect();
+        let allergens = match parts.next() {
+            Some(a) => a.strip_suffix(')').ok_or_else(|| src.error(&a[a.len()..], "')'"))?
+                .split(", ").map(|s| s.to_string()).collect(),
+            None => Vec::new(),
+        };
+        Ok(Food{ ingredients, allergens })
+    }).collect()
+}
+
+
+/// Intersects the ingredients of all foods listing an allergen.
+/// Returns the ingredients which may contain it, for each allergen.
+fn candidates(foods: &[Food]) -> HashMap<&str, HashSet<&str>> {
+    let mut cands: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for food in foods.iter() {
+        let ingredients: HashSet<&str> = food.ingredients.iter().map(|s| s.as_str()).collect();
+        for a in food.allergens.iter() {
+            cands.entry(a)
+                .and_modify(|c| c.retain(|i| ingredients.contains(i)))
+                .or_insert_with(|| ingredients.clone());
+        }
+    }
+    cands
+}
+
+
+#[aoc(day21, part1)]
+pub fn solve_part1(input: &[Food]) -> usize {
+    let cands = candidates(input);
+    let unsafe_ingredients: HashSet<&str> = cands.values().flatten().cloned().collect();
+    input.iter().flat_map(|f| f.ingredients.iter())
+        .filter(|i| !unsafe_ingredients.contains(i.as_str()))
+        .count()
+}
+
+
+/// Resolves which ingredient contains which allergen, by repeatedly fixing allergens
+/// with a single candidate and removing that ingredient from the other allergens.
+fn resolve<'a>(mut cands: HashMap<&'a str, HashSet<&'a str>>) -> Result<BTreeMap<&'a str, &'a str>, MappingError> {
+    let mut mapping = BTreeMap::new();
+    while !cands.is_empty() {
+        if let Some((&a, _)) = cands.iter().find(|(_, c)| c.is_empty()) {
+            return Err(MappingError::Impossible(a.to_string()));
+        }
+        let mut fixed: Vec<(&str, &str)> = cands.iter()
+            .filter(|(_, c)| c.len() == 1)
+            .map(|(&a, c)| (a, *c.iter().next().unwrap()))
+            .collect();
+        fixed.sort();
+        // each ingredient contains at most one allergen
+        for (k, &(a, i)) in fixed.iter().enumerate() {
+            if fixed[..k].iter().any(|&(_, j)| j == i) {
+                return Err(MappingError::Impossible(a.to_string()));
+            }
+        }
+        if fixed.is_empty() {
+            let mut open: Vec<String> = cands.keys().map(|a| a.to_string()).collect();
+            open.sort();
+            return Err(MappingError::Ambiguous(open));
+        }
+        for (a, i) in fixed {
+            mapping.insert(a, i);
+            cands.remove(a);
+            for c in cands.values_mut() {
+                c.remove(i);
+            }
+        }
+    }
+    Ok(mapping)
+}
+
+
+#[aoc(day21, part2)]
+pub fn solve_part2(input: &[Food]) -> Result<String, MappingError> {
+    let mapping = resolve(candidates(input))?;
+    Ok(mapping.values().cloned().collect::<Vec<&str>>().join(","))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)";
+
+    #[test]
+    fn test_parser() {
+        let foods = parse_foods("a b (contains x, y)\nc").unwrap();
+        assert_eq!(foods[0].ingredients, ["a", "b"].iter().map(|s| s.to_string()).collect());
+        assert_eq!(foods[0].allergens, vec!["x".to_string(), "y".to_string()]);
+        assert_eq!(foods[1].allergens, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_part1_solver() {
+        assert_eq!(solve_part1(&parse_foods(TEST_INPUT).unwrap()), 5);
+    }
+
+    #[test]
+    fn test_part2_solver() {
+        assert_eq!(solve_part2(&parse_foods(TEST_INPUT).unwrap()), Ok("mxmxvkd,sqjhc,fvjkl".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_errors() {
+        let foods = parse_foods("a b (contains x, y)\nc (contains z)").unwrap();
+        let err = solve_part2(&foods).unwrap_err();
+        assert_eq!(err, MappingError::Ambiguous(vec!["x".to_string(), "y".to_string()]));
+        assert_eq!(err.to_string(), "ambiguous ingredients for allergens: x, y");
+        let foods = parse_foods("a (contains x)\nb (contains x)").unwrap();
+        assert_eq!(solve_part2(&foods), Err(MappingError::Impossible("x".to_string())));
+        // both allergens are left with the same ingredient
+        let foods = parse_foods("a (contains x, y)").unwrap();
+        assert_eq!(solve_part2(&foods), Err(MappingError::Impossible("y".to_string())));
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+use crate::parse::{Input, ParseError};
+
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Deck(VecDeque<u32>);
+
+
+impl Deck {
+    fn draw(&mut self) -> Option<u32> {
+        self.0.pop_front()
+    }
+
+    /// Puts the won cards at the bottom of the deck, the own card first.
+    fn take(&mut self, own: u32, other: u32) {
+        self.0.push_back(own);
+        self.0.push_back(other);
+    }
+
+    /// Copy of the first n cards.
+    fn sub_deck(&self, n: usize) -> Deck {
+        Deck(self.0.iter().take(n).cloned().collect())
+    }
+
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn score(&self) -> u64 {
+        self.0.iter().rev().enumerate().map(|(i, &c)| (i as u64 + 1) * c as u64).sum()
+    }
+}
+
+
+impl fmt::Display for Deck {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cards: Vec<String> = self.0.iter().map(|c| c.to_string()).collect();
+        write!(f, "{}", cards.join(", "))
+    }
+}
+
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Player {
+    One,
+    Two,
+}
+
+
+impl fmt::Display for Player {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Player::One => write!(f, "Player 1"),
+            Player::Two => write!(f, "Player 2"),
+        }
+    }
+}
+
+
+/// A single round with the decks before the cards were drawn.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Round {
+    game: usize,
+    round: usize,
+    decks: (Deck, Deck),
+    winner: Player,
+}
+
+
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "-- Round {} (Game {}) --", self.round, self.game)?;
+        writeln!(f, "Player 1's deck: {}", self.decks.0)?;
+        writeln!(f, "Player 2's deck: {}", self.decks.1)?;
+        writeln!(f, "Player 1 plays: {}", self.decks.0.0[0])?;
+        writeln!(f, "Player 2 plays: {}", self.decks.1.0[0])?;
+        write!(f, "{} wins round {} of game {}!", self.winner, self.round, self.game)
+    }
+}
+
+
+/// Plays Combat, optionally recursive, and keeps track of the games played.
+pub struct Game {
+    recursive: bool,
+    /// Winners of sub-games by their initial decks
+    memo: HashMap<(Deck, Deck), Player>,
+    games: usize,
+    log: Option<Vec<Round>>,
+}
+
+
+impl Game {
+    pub fn new(recursive: bool) -> Game {
+        Game { recursive, memo: HashMap::new(), games: 0, log: None }
+    }
+
+    /// Records every round played. Sub-games answered from the memo are not played again,
+    /// so their rounds only appear the first time.
+    pub fn with_log(mut self) -> Game {
+        self.log = Some(Vec::new());
+        self
+    }
+
+    pub fn log(&self) -> Option<&[Round]> {
+        self.log.as_deref()
+    }
+
+    /// Plays a game to the end, returns the winner and their deck.
+    pub fn play(&mut self, d1: &Deck, d2: &Deck) -> (Player, Deck) {
+        let (winner, d1, d2) = self.play_game(d1.clone(), d2.clone());
+        match winner {
+            Player::One => (winner, d1),
+            Player::Two => (winner, d2),
+        }
+    }
+
+    fn play_game(&mut self, mut d1: Deck, mut d2: Deck) -> (Player, Deck, Deck) {
+        self.games += 1;
+        let game = self.games;
+        let mut seen = HashSet::new();
+        let mut round = 0;
+        loop {
+            if d1.is_empty() {
+                return (Player::Two, d1, d2);
+            }
+            if d2.is_empty() {
+                return (Player::One, d1, d2);
+            }
+            // a repeated configuration would loop forever, player 1 wins the game instead
+            if self.recursive && !seen.insert((d1.clone(), d2.clone())) {
+                return (Player::One, d1, d2);
+            }
+            round += 1;
+            // rounds are logged when they start, before the rounds of their sub-games,
+            // the winner is filled in once the round is decided
+            let entry = self.log.as_mut().map(|log| {
+                log.push(Round { game, round, decks: (d1.clone(), d2.clone()), winner: Player::One });
+                log.len() - 1
+            });
+
+            let c1 = d1.draw().unwrap();
+            let c2 = d2.draw().unwrap();
+            let winner = if self.recursive && d1.len() >= c1 as usize && d2.len() >= c2 as usize {
+                self.sub_game(d1.sub_deck(c1 as usize), d2.sub_deck(c2 as usize))
+            } else if c1 > c2 {
+                Player::One
+            } else {
+                Player::Two
+            };
+            match winner {
+                Player::One => d1.take(c1, c2),
+                Player::Two => d2.take(c2, c1),
+            }
+
+            if let (Some(log), Some(i)) = (self.log.as_mut(), entry) {
+                log[i].winner = winner;
+            }
+        }
+    }
+
+    fn sub_game(&mut self, d1: Deck, d2: Deck) -> Player {
+        let key = (d1, d2);
+        if let Some(&winner) = self.memo.get(&key) {
+            return winner;
+        }
+        let (winner, _, _) = self.play_game(key.0.clone(), key.1.clone());
+        self.memo.insert(key, winner);
+        winner
+    }
+}
+
+
+#[aoc_generator(day22)]
+fn parse_decks(input: &str) -> Result<(Deck, Deck), ParseError> {
+    let src = Input::new(22, input);
+    let mut parts = input.split("\n\n");
+    let mut deck = |after: &str, header: &str| {
+        let p = src.expect(parts.next(), after, header)?;
+        let first = p.lines().next();
+        if first != Some(header) {
+            // an empty section has no first line, report the header missing at its start
+            return Err(src.error(first.unwrap_or(&p[..0]), header));
+        }
+        let cards = p.lines().skip(1).map(|l| src.parse(l, "a card")).collect::<Result<_, ParseError>>()?;
+        Ok((p, Deck(cards)))
+    };
+    let (p1, d1) = deck(input, "Player 1:")?;
+    let (_, d2) = deck(p1, "Player 2:")?;
+    Ok((d1, d2))
+}
+
+
+#[aoc(day22, part1)]
+pub fn solve_part1(input: &(Deck, Deck)) -> u64 {
+    Game::new(false).play(&input.0, &input.1).1.score()
+}
+
+
+#[aoc(day22, part2)]
+pub fn solve_part2(input: &(Deck, Deck)) -> u64 {
+    Game::new(true).play(&input.0, &input.1).1.score()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "Player 1:\n9\n2\n6\n3\n1\n\nPlayer 2:\n5\n8\n4\n7\n10";
+
+    fn deck(cards: &[u32]) -> Deck {
+        Deck(cards.iter().cloned().collect())
+    }
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(parse_decks(TEST_INPUT).unwrap(), (deck(&[9, 2, 6, 3, 1]), deck(&[5, 8, 4, 7, 10])));
+        let err = parse_decks("").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (1, 1, "Player 1:"));
+        let err = parse_decks("\n\nPlayer 2:\n1").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str(), err.expected.as_str()), (1, 1, "", "Player 1:"));
+        let err = parse_decks("Player 1:\n1\n\nPlayer 3:\n2").unwrap_err();
+        assert_eq!((err.line, err.text.as_str(), err.expected.as_str()), (4, "Player 3:", "Player 2:"));
+        let err = parse_decks("Player 1:\n1\n\n").unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (4, "Player 2:"));
+    }
+
+    #[test]
+    fn test_score() {
+        assert_eq!(deck(&[3, 2, 10, 6, 8, 5, 9, 4, 7, 1]).score(), 306);
+    }
+
+    #[test]
+    fn test_part1_solver() {
+        assert_eq!(solve_part1(&parse_decks(TEST_INPUT).unwrap()), 306);
+    }
+
+    #[test]
+    fn test_part2_solver() {
+        assert_eq!(solve_part2(&parse_decks(TEST_INPUT).unwrap()), 291);
+    }
+
+    #[test]
+    fn test_infinite_game() {
+        let (winner, _) = Game::new(true).play(&deck(&[43, 19]), &deck(&[2, 29, 14]));
+        assert_eq!(winner, Player::One);
+    }
+
+    #[test]
+    fn test_log() {
+        let (d1, d2) = parse_decks(TEST_INPUT).unwrap();
+        let mut game = Game::new(false).with_log();
+        game.play(&d1, &d2);
+        let log = game.log().unwrap();
+        assert_eq!(log.len(), 29);
+        assert_eq!(log[0].to_string(), "-- Round 1 (Game 1) --
+Player 1's deck: 9, 2, 6, 3, 1
+Player 2's deck: 5, 8, 4, 7, 10
+Player 1 plays: 9
+Player 2 plays: 5
+Player 1 wins round 1 of game 1!");
+        let mut game = Game::new(true).with_log();
+        game.play(&d1, &d2);
+        let log = game.log().unwrap();
+        assert_eq!(log[8].to_string(), "-- Round 9 (Game 1) --
+Player 1's deck: 4, 9, 8, 5, 2
+Player 2's deck: 3, 10, 1, 7, 6
+Player 1 plays: 4
+Player 2 plays: 3
+Player 2 wins round 9 of game 1!");
+        assert_eq!((log[9].game, log[9].round), (2, 1));
+        assert!(log[9..].iter().any(|r| r.game == 1 && r.round == 10));
+        assert!(Game::new(true).log().is_none());
+    }
+}
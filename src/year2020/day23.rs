@@ -0,0 +1,184 @@
+use crate::parse::{Input, ParseError};
+
+
+/// Circle of cups labeled 1 to n, stored as successor table:
+/// next[label] is the label of the cup clockwise of it, next[0] is unused.
+/// Moving cups only relinks three successors, so each move is constant time.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CupRing {
+    next: Vec<u32>,
+    current: u32,
+}
+
+
+impl CupRing {
+    /// Places the labels in a circle, followed by the labels max+1 to total
+    /// if total is greater than the number of labels.
+    /// Labels have to be the numbers 1 to labels.len() in any order.
+    /// A move needs at least five cups, the current one, three picked up and a destination.
+    pub fn new(labels: &[u32], total: usize) -> CupRing {
+        let n = total.max(labels.len()) as u32;
+        assert!(n >= 5, "A ring needs at least 5 cups, not {}", n);
+        let order: Vec<u32> = labels.iter().cloned().chain(labels.len() as u32 + 1..=n).collect();
+        let mut next = vec![0; n as usize + 1];
+        for (i, &l) in order.iter().enumerate() {
+            next[l as usize] = order[(i + 1) % order.len()];
+        }
+        CupRing { next, current: order[0] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.next.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn after(&self, label: u32) -> u32 {
+        self.next[label as usize]
+    }
+
+    /// Picks up the three cups after the current one, places them after the destination cup
+    /// and selects the cup after the current one as new current cup.
+    pub fn mv(&mut self) {
+        let n = self.len() as u32;
+        let a = self.after(self.current);
+        let b = self.after(a);
+        let c = self.after(b);
+
+        let mut dest = self.current;
+        loop {
+            dest = if dest == 1 { n } else { dest - 1 };
+            if dest != a && dest != b && dest != c {
+                break;
+            }
+        }
+
+        self.next[self.current as usize] = self.after(c);
+        self.next[c as usize] = self.after(dest);
+        self.next[dest as usize] = a;
+        self.current = self.after(self.current);
+    }
+
+    /// Iterates over all cups clockwise, starting with the current one.
+    pub fn iter(&self) -> Cups<'_> {
+        Cups { ring: self, next: self.current, remaining: self.len() }
+    }
+
+    /// Iterates clockwise over all cups after the given one.
+    pub fn labels_after(&self, label: u32) -> Cups<'_> {
+        Cups { ring: self, next: self.after(label), remaining: self.len() - 1 }
+    }
+}
+
+
+pub struct Cups<'a> {
+    ring: &'a CupRing,
+    next: u32,
+    remaining: usize,
+}
+
+
+impl<'a> Iterator for Cups<'a> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let label = self.next;
+        self.next = self.ring.after(label);
+        Some(label)
+    }
+}
+
+
+#[aoc_generator(day23)]
+fn parse_labels(input: &str) -> Result<Vec<u32>, ParseError> {
+    let src = Input::new(23, input);
+    let labels = input.trim();
+    if labels.len() < 5 {
+        return Err(src.error(&labels[labels.len()..], "at least 5 labels"));
+    }
+    let mut seen = vec![false; labels.len() + 1];
+    labels.char_indices().map(|(i, c)| {
+        let part = &labels[i..i + c.len_utf8()];
+        // the ring needs each of the labels 1 to n exactly once
+        match c.to_digit(10) {
+            Some(d) if d >= 1 && (d as usize) < seen.len() && !seen[d as usize] => {
+                seen[d as usize] = true;
+                Ok(d)
+            },
+            _ => Err(src.error(part, &format!("each of the labels 1 to {} once", labels.len()))),
+        }
+    }).collect()
+}
+
+
+#[aoc(day23, part1)]
+pub fn solve_part1(input: &[u32]) -> String {
+    let mut ring = CupRing::new(input, input.len());
+    for _ in 0..100 {
+        ring.mv();
+    }
+    ring.labels_after(1).map(|l| l.to_string()).collect()
+}
+
+
+#[aoc(day23, part2)]
+pub fn solve_part2(input: &[u32]) -> u64 {
+    let mut ring = CupRing::new(input, 1_000_000);
+    for _ in 0..10_000_000 {
+        ring.mv();
+    }
+    ring.labels_after(1).take(2).map(|l| l as u64).product()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: [u32; 9] = [3, 8, 9, 1, 2, 5, 4, 6, 7];
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(parse_labels("389125467").unwrap(), TEST_INPUT.to_vec());
+        assert_eq!(parse_labels("12").unwrap_err().expected, "at least 5 labels");
+        assert_eq!(parse_labels("").unwrap_err().expected, "at least 5 labels");
+        assert!(parse_labels("13452").is_ok());
+        assert!(parse_labels("13462").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "at least 5 cups")]
+    fn test_ring_too_small() {
+        CupRing::new(&[1, 2], 4);
+    }
+
+    #[test]
+    fn test_ring() {
+        let mut ring = CupRing::new(&TEST_INPUT, 9);
+        assert_eq!(ring.iter().collect::<Vec<u32>>(), TEST_INPUT.to_vec());
+        ring.mv();
+        assert_eq!(ring.iter().collect::<Vec<u32>>(), vec![2, 8, 9, 1, 5, 4, 6, 7, 3]);
+        ring.mv();
+        assert_eq!(ring.iter().collect::<Vec<u32>>(), vec![5, 4, 6, 7, 8, 9, 1, 3, 2]);
+        assert_eq!(ring.labels_after(1).collect::<Vec<u32>>(), vec![3, 2, 5, 4, 6, 7, 8, 9]);
+        let ring = CupRing::new(&TEST_INPUT, 12);
+        assert_eq!(ring.iter().skip(8).collect::<Vec<u32>>(), vec![7, 10, 11, 12]);
+        assert_eq!(ring.labels_after(12).next(), Some(3));
+    }
+
+    #[test]
+    fn test_part1_solver() {
+        assert_eq!(solve_part1(&TEST_INPUT), "67384529");
+    }
+
+    #[test]
+    fn test_part2_solver() {
+        assert_eq!(solve_part2(&TEST_INPUT), 149245887792);
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::HashSet;
+
+use crate::life::{self, Cell, Rule};
+use crate::parse::{Input, ParseError};
+
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum HexDir {
+    E,
+    SE,
+    SW,
+    W,
+    NW,
+    NE,
+}
+
+
+use HexDir::*;
+
+
+const DIRECTIONS: [HexDir; 6] = [E, SE, SW, W, NW, NE];
+
+
+/// Hex tile in axial coordinates (q, r), q grows to the east, r to the south east.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Hex(i32, i32);
+
+
+impl Hex {
+    fn go(&self, dir: HexDir) -> Hex {
+        let (dq, dr) = match dir {
+            E => (1, 0),
+            SE => (0, 1),
+            SW => (-1, 1),
+            W => (-1, 0),
+            NW => (0, -1),
+            NE => (1, -1),
+        };
+        Hex(self.0 + dq, self.1 + dr)
+    }
+}
+
+
+impl Cell for Hex {
+    fn neighbours(&self) -> Vec<Hex> {
+        DIRECTIONS.iter().map(|&d| self.go(d)).collect()
+    }
+}
+
+
+#[aoc_generator(day24)]
+fn parse_paths(input: &str) -> Result<Vec<Vec<HexDir>>, ParseError> {
+    let src = Input::new(24, input);
+    input.lines().map(|l| {
+        let mut path = Vec::new();
+        let mut rest = l;
+        while !rest.is_empty() {
+            let (dir, len) = match rest.as_bytes() {
+                [b'e', ..] => (E, 1),
+                [b'w', ..] => (W, 1),
+                [b's', b'e', ..] => (SE, 2),
+                [b's', b'w', ..] => (SW, 2),
+                [b'n', b'e', ..] => (NE, 2),
+                [b'n', b'w', ..] => (NW, 2),
+                _ => {
+                    let c = rest.chars().next().unwrap();
+                    return Err(src.error(&rest[..c.len_utf8()], "one of e, se, sw, w, nw, ne"));
+                },
+            };
+            path.push(dir);
+            rest = &rest[len..];
+        }
+        Ok(path)
+    }).collect()
+}
+
+
+/// Follows each path from the reference tile and flips the tile at its end.
+/// Returns the tiles which are black afterwards.
+fn flip_tiles(paths: &[Vec<HexDir>]) -> HashSet<Hex> {
+    let mut black = HashSet::new();
+    for path in paths.iter() {
+        let tile = path.iter().fold(Hex(0, 0), |h, &d| h.go(d));
+        if !black.remove(&tile) {
+            black.insert(tile);
+        }
+    }
+    black
+}
+
+
+#[aoc(day24, part1)]
+pub fn solve_part1(input: &[Vec<HexDir>]) -> usize {
+    flip_tiles(input).len()
+}
+
+
+#[aoc(day24, part2)]
+pub fn solve_part2(input: &[Vec<HexDir>]) -> usize {
+    let mut black = flip_tiles(input);
+    for _ in 0..100 {
+        black = life::step(&black, Rule::LOBBY);
+    }
+    black.len()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "sesenwnenenewseeswwswswwnenewsewsw
+neeenesenwnwwswnenewnwwsewnenwseswesw
+seswneswswsenwwnwse
+nwnwneseeswswnenewneswwnewseswneseene
+swweswneswnenwsewnwneneseenw
+eesenwseswswnenwswnwnwsewwnwsene
+sewnenenenesenwsewnenwwwse
+wenwwweseeeweswwwnwwe
+wsweesenenewnwwnwsenewsenwwsesesenwne
+neeswseenwwswnwswswnw
+nenwswwsewswnenenewsenwsenwnesesenew
+enewnwewneswsewnwswenweswnenwsenwsw
+sweneswneswneneenwnewenewwneswswnese
+swwesenesewenwneswnwwneseswwne
+enesenwswwswneneswsenwnewswseenwsese
+wnwnesenesenenwwnenwsewesewsesesew
+nenewswnwewswnenesenwnesewesw
+eneswnwswnwsenenwnwnwwseeswneewsenese
+neswnwewnwnwseenwseesewsenwsweewe
+wseweeenwnesenwwwswnew";
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(parse_paths("esenee\nnwwswee").unwrap(), vec![vec![E, SE, NE, E], vec![NW, W, SW, E, E]]);
+        let err = parse_paths("esenee\nwenx").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "n"));
+    }
+
+    #[test]
+    fn test_part1_solver() {
+        assert_eq!(solve_part1(&parse_paths(TEST_INPUT).unwrap()), 10);
+    }
+
+    #[test]
+    fn test_paths() {
+        let paths = parse_paths("nwwswee\nesew").unwrap();
+        assert_eq!(flip_tiles(&paths[..1]), [Hex(0, 0)].iter().cloned().collect());
+        assert_eq!(flip_tiles(&paths[1..]), [Hex(0, 1)].iter().cloned().collect());
+        // the reference tile is flipped twice
+        assert_eq!(solve_part1(&parse_paths("nwwswee\nesew\nee\nnwwswee").unwrap()), 2);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let h = Hex(2, -1);
+        assert!(h.neighbours().iter().all(|n| n.neighbours().contains(&h)));
+        assert_eq!(h.neighbours().iter().collect::<HashSet<&Hex>>().len(), 6);
+    }
+
+    #[test]
+    fn test_step() {
+        // three black tiles around a common corner each add a black tile on the far side of a pair
+        let black = flip_tiles(&parse_paths("e\nse\nnwse").unwrap());
+        let expect: HashSet<Hex> = [Hex(0, 0), Hex(1, 0), Hex(0, 1), Hex(1, -1), Hex(-1, 1), Hex(1, 1)]
+            .iter().cloned().collect();
+        assert_eq!(life::step(&black, Rule::LOBBY), expect);
+        // a single black tile has no black neighbours and flips back
+        assert_eq!(solve_part2(&parse_paths("esew").unwrap()), 0);
+    }
+
+    #[test]
+    fn test_part2_solver() {
+        assert_eq!(solve_part2(&parse_paths(TEST_INPUT).unwrap()), 2208);
+    }
+}
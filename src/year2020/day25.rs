@@ -0,0 +1,56 @@
+use crate::numtheory::{discrete_log, mod_pow};
+use crate::parse::{Input, ParseError};
+
+
+const MODULUS: u64 = 20201227;
+const SUBJECT: u64 = 7;
+
+
+#[aoc_generator(day25)]
+fn parse_keys(input: &str) -> Result<(u64, u64), ParseError> {
+    let src = Input::new(25, input);
+    let mut lines = input.lines();
+    let card = src.expect(lines.next(), input, "the card's public key")?;
+    let door = src.expect(lines.next(), card, "the door's public key")?;
+    Ok((src.parse(card.trim(), "a public key")?, src.parse(door.trim(), "a public key")?))
+}
+
+
+/// Recovers the loop size which transforms the subject number 7 into the public key.
+fn loop_size(public_key: u64) -> Option<u64> {
+    discrete_log(SUBJECT, public_key, MODULUS)
+}
+
+
+#[aoc(day25, part1)]
+pub fn solve_part1(input: &(u64, u64)) -> Option<u64> {
+    let card_loop = loop_size(input.0)?;
+    Some(mod_pow(input.1, card_loop, MODULUS))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "5764801\n17807724";
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(parse_keys(TEST_INPUT).unwrap(), (5764801, 17807724));
+    }
+
+    #[test]
+    fn test_loop_size() {
+        assert_eq!(loop_size(5764801), Some(8));
+        assert_eq!(loop_size(17807724), Some(11));
+    }
+
+    #[test]
+    fn test_part1_solver() {
+        assert_eq!(solve_part1(&parse_keys(TEST_INPUT).unwrap()), Some(14897079));
+        // both sides arrive at the same key
+        let door_loop = loop_size(17807724).unwrap();
+        assert_eq!(mod_pow(5764801, door_loop, MODULUS), 14897079);
+    }
+}
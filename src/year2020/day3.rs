@@ -0,0 +1,70 @@
+use crate::grid::Grid;
+use crate::parse::{Input, ParseError};
+
+#[aoc_generator(day3)]
+fn parse_field(input: &str) -> Result<Grid<bool>, ParseError> {
+    let src = Input::new(3, input);
+    Grid::parse(&src, input, "'#' or '.'", |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+}
+
+/// The field repeats to the right, so the x coordinate wraps around.
+fn number_of_trees(field: &Grid<bool>, slope: (usize, usize)) -> u64 {
+    let (vx, vy) = slope;
+    (0..(field.height()/vy)).filter(|&i| *field.get_wrapped((vx*i) as isize, (vy*i) as isize)).count() as u64
+}
+
+#[aoc(day3, part1)]
+pub fn solve_part1(input: &Grid<bool>) -> u64 {
+    number_of_trees(input, (3, 1))
+}
+
+#[aoc(day3, part2)]
+pub fn solve_part2(input: &Grid<bool>) -> u64 {
+    let slopes = vec![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+    slopes.into_iter().map(|s| number_of_trees(input, s)).product()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "..##.......\n#...#...#..\n.#....#..#.
+..#.#...#.#\n.#...##..#.\n..#.##.....\n.#.#.#....#
+.#........#\n#.##...#...\n#...##....#\n.#..#...#.#";
+
+    #[test]
+    fn test_parser() {
+        let input = ".#\n#.";
+        assert_eq!(parse_field(input).unwrap(), Grid::from_rows(vec![vec![false, true], vec![true, false]]));
+    }
+
+    #[test]
+    fn test_part1_solver() {
+        let input1 = Grid::from_rows(vec![vec![false, true, false],
+                                          vec![true, true, false],
+                                          vec![false, true, true]]);
+        assert_eq!(solve_part1(&input1), 1);
+        let input = Grid::from_rows(vec![vec![false, true, false, false],
+                                         vec![true, true, false, true],
+                                         vec![true, true, true, false]]);
+        assert_eq!(solve_part1(&input), 2);
+    }
+
+    #[test]
+    fn test_part1_solver_large() {
+        let input = parse_field(TEST_INPUT).unwrap();
+        assert_eq!(solve_part1(&input), 7);
+    }
+
+
+    #[test]
+    fn test_part2_solver_large() {
+        let input = parse_field(TEST_INPUT).unwrap();
+        assert_eq!(solve_part2(&input), 336);
+    }
+}
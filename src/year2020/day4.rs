@@ -0,0 +1,300 @@
+use std::fmt;
+
+use crate::parse::{Input, ParseError};
+
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Field {
+    Byr,
+    Iyr,
+    Eyr,
+    Hgt,
+    Hcl,
+    Ecl,
+    Pid,
+    Cid,
+}
+
+
+impl Field {
+    fn name(&self) -> &'static str {
+        match self {
+            Field::Byr => "byr",
+            Field::Iyr => "iyr",
+            Field::Eyr => "eyr",
+            Field::Hgt => "hgt",
+            Field::Hcl => "hcl",
+            Field::Ecl => "ecl",
+            Field::Pid => "pid",
+            Field::Cid => "cid",
+        }
+    }
+}
+
+
+#[derive(Debug, PartialEq)]
+pub struct ValidationError {
+    field: Field,
+    reason: String,
+}
+
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.field.name(), self.reason)
+    }
+}
+
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Passport {
+    byr: Option<String>,
+    iyr: Option<String>,
+    eyr: Option<String>,
+    hgt: Option<String>,
+    hcl: Option<String>,
+    ecl: Option<String>,
+    pid: Option<String>,
+    cid: Option<String>,
+}
+
+
+fn validate_year(s: &str, low: u32, high: u32) -> Result<(), String> {
+    match s.parse::<u32>() {
+        Ok(y) if s.len() == 4 && low <= y && y <= high => Ok(()),
+        _ => Err(format!("expected year between {} and {}, found '{}'", low, high, s)),
+    }
+}
+
+fn validate_byr(s: &str) -> Result<(), String> {
+    validate_year(s, 1920, 2002)
+}
+
+fn validate_iyr(s: &str) -> Result<(), String> {
+    validate_year(s, 2010, 2020)
+}
+
+fn validate_eyr(s: &str) -> Result<(), String> {
+    validate_year(s, 2020, 2030)
+}
+
+fn validate_hgt(s: &str) -> Result<(), String> {
+    let (num, low, high) = if let Some(n) = s.strip_suffix("cm") {
+        (n, 150, 193)
+    } else if let Some(n) = s.strip_suffix("in") {
+        (n, 59, 76)
+    } else {
+        return Err(format!("expected number followed by cm or in, found '{}'", s));
+    };
+    match num.parse::<u32>() {
+        Ok(h) if low <= h && h <= high => Ok(()),
+        _ => Err(format!("expected height between {} and {}, found '{}'", low, high, s)),
+    }
+}
+
+fn validate_hcl(s: &str) -> Result<(), String> {
+    match s.strip_prefix('#') {
+        Some(hex) if hex.len() == 6 && hex.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')) => Ok(()),
+        _ => Err(format!("expected # followed by six hex digits, found '{}'", s)),
+    }
+}
+
+fn validate_ecl(s: &str) -> Result<(), String> {
+    match s {
+        "amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth" => Ok(()),
+        _ => Err(format!("expected one of amb, blu, brn, gry, grn, hzl, oth, found '{}'", s)),
+    }
+}
+
+fn validate_pid(s: &str) -> Result<(), String> {
+    if s.len() == 9 && s.chars().all(|c| c.is_ascii_digit()) {
+        Ok(())
+    } else {
+        Err(format!("expected nine digit number, found '{}'", s))
+    }
+}
+
+fn validate_cid(_: &str) -> Result<(), String> {
+    Ok(())
+}
+
+
+type Validator = fn(&str) -> Result<(), String>;
+
+
+impl Passport {
+    /// Sets a field by its name, returns false for unknown fields.
+    fn set(&mut self, key: &str, value: &str) -> bool {
+        let field = match key {
+            "byr" => &mut self.byr,
+            "iyr" => &mut self.iyr,
+            "eyr" => &mut self.eyr,
+            "hgt" => &mut self.hgt,
+            "hcl" => &mut self.hcl,
+            "ecl" => &mut self.ecl,
+            "pid" => &mut self.pid,
+            "cid" => &mut self.cid,
+            _ => return false,
+        };
+        *field = Some(value.to_string());
+        true
+    }
+
+    /// Fields with their value, whether they are required and their validator.
+    fn fields(&self) -> [(Field, &Option<String>, bool, Validator); 8] {
+        [(Field::Byr, &self.byr, true, validate_byr),
+         (Field::Iyr, &self.iyr, true, validate_iyr),
+         (Field::Eyr, &self.eyr, true, validate_eyr),
+         (Field::Hgt, &self.hgt, true, validate_hgt),
+         (Field::Hcl, &self.hcl, true, validate_hcl),
+         (Field::Ecl, &self.ecl, true, validate_ecl),
+         (Field::Pid, &self.pid, true, validate_pid),
+         (Field::Cid, &self.cid, false, validate_cid)]
+    }
+
+    /// Checks that all required fields are present.
+    fn check_present(&self) -> Result<(), ValidationError> {
+        for (field, value, required, _) in self.fields().iter() {
+            if *required && value.is_none() {
+                return Err(ValidationError{ field: *field, reason: "missing".to_string() });
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that all required fields are present and all fields have valid values.
+    fn validate(&self) -> Result<(), ValidationError> {
+        self.check_present()?;
+        for (field, value, _, validator) in self.fields().iter() {
+            if let Some(v) = value {
+                validator(v).map_err(|reason| ValidationError{ field: *field, reason })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+
+#[aoc_generator(day4)]
+fn parse_passports(input: &str) -> Result<Vec<Passport>, ParseError> {
+    let src = Input::new(4, input);
+    input.split("\n\n").map(|record| {
+        let mut p = Passport::default();
+        for entry in record.split_whitespace() {
+            let mut kv = entry.splitn(2, ':');
+            let k = kv.next().unwrap();
+            let v = src.expect(kv.next(), entry, "<field>:<value>")?;
+            if !p.set(k, v) {
+                return Err(src.error(k, "a passport field"));
+            }
+        }
+        Ok(p)
+    }).collect()
+}
+
+
+#[aoc(day4, part1)]
+pub fn solve_part1(input: &[Passport]) -> usize {
+    input.iter().filter(|p| p.check_present().is_ok()).count()
+}
+
+
+#[aoc(day4, part2)]
+pub fn solve_part2(input: &[Passport]) -> usize {
+    input.iter().filter(|p| p.validate().is_ok()).count()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in";
+
+    const INVALID: &str = "eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007";
+
+    const VALID: &str = "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719";
+
+    #[test]
+    fn test_parser() {
+        let p = &parse_passports("hcl:#cfa07d byr:1929\niyr:2013").unwrap()[0];
+        assert_eq!(p.hcl, Some("#cfa07d".to_string()));
+        assert_eq!(p.byr, Some("1929".to_string()));
+        assert_eq!(p.iyr, Some("2013".to_string()));
+        assert_eq!(p.cid, None);
+    }
+
+    #[test]
+    fn test_validators() {
+        assert!(validate_byr("2002").is_ok());
+        assert!(validate_byr("2003").is_err());
+        assert!(validate_hgt("60in").is_ok());
+        assert!(validate_hgt("190cm").is_ok());
+        assert!(validate_hgt("190in").is_err());
+        assert!(validate_hgt("190").is_err());
+        assert!(validate_hcl("#123abc").is_ok());
+        assert!(validate_hcl("#123abz").is_err());
+        assert!(validate_hcl("123abc").is_err());
+        assert!(validate_ecl("brn").is_ok());
+        assert!(validate_ecl("wat").is_err());
+        assert!(validate_pid("000000001").is_ok());
+        assert!(validate_pid("0123456789").is_err());
+    }
+
+    #[test]
+    fn test_validation_error() {
+        let passports = parse_passports(TEST_INPUT).unwrap();
+        let err = passports[1].check_present().unwrap_err();
+        assert_eq!(err.to_string(), "hgt: missing");
+        let passports = parse_passports(INVALID).unwrap();
+        let err = passports[0].validate().unwrap_err();
+        assert_eq!(err.field, Field::Eyr);
+        assert_eq!(err.to_string(), "eyr: expected year between 2020 and 2030, found '1972'");
+    }
+
+    #[test]
+    fn test_part1_solver() {
+        assert_eq!(solve_part1(&parse_passports(TEST_INPUT).unwrap()), 2);
+    }
+
+    #[test]
+    fn test_part2_solver() {
+        assert_eq!(solve_part2(&parse_passports(INVALID).unwrap()), 0);
+        assert_eq!(solve_part2(&parse_passports(VALID).unwrap()), 4);
+    }
+}
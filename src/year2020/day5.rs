@@ -0,0 +1,52 @@
+use itertools::sorted;
+
+use crate::parse::{Input, ParseError};
+
+
+#[aoc_generator(day5)]
+fn parse_seats(input: &str) -> Result<Vec<u16>, ParseError> {
+    let src = Input::new(5, input);
+    input.lines().map(|l| {
+        let bits = l.char_indices().map(|(i, c)| match c {
+            'F' => Ok('0'),
+            'B' => Ok('1'),
+            'L' => Ok('0'),
+            'R' => Ok('1'),
+            _ => Err(src.error(&l[i..i + c.len_utf8()], "one of F, B, L, R")),
+        }).collect::<Result<String, ParseError>>()?;
+        u16::from_str_radix(&bits, 2).map_err(|_| src.error(l, "a seat code of up to 16 letters"))
+    }).collect()
+}
+
+#[aoc(day5, part1)]
+pub fn solve_part1(input: &[u16]) -> u16 {
+    *input.iter().max().unwrap()
+}
+
+#[aoc(day5, part2)]
+pub fn solve_part2(input: &[u16]) -> u16 {
+    let ids: Vec<&u16> = sorted(input).collect();
+    for (&a, &b) in ids.iter().zip(ids[1..].iter()) {
+        if *a != b - 1 {
+            return b -1
+        }
+    }
+    panic!("No free seat found");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "BFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL";
+
+    #[test]
+    fn test_parser() {
+        assert_eq!(parse_seats(TEST_INPUT).unwrap(), vec![567, 119, 820]);
+    }
+
+    #[test]
+    fn test_part1_solver() {
+        assert_eq!(solve_part1(&parse_seats(TEST_INPUT).unwrap()), 820);
+    }
+}
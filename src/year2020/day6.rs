@@ -1,5 +1,7 @@
 use itertools::Itertools;
 
+use crate::parse::{Input, ParseError};
+
 
 pub type Group = Vec<String>;
 
@@ -14,25 +16,31 @@ fn count_all(group: &Group) -> usize {
 }
 
 #[aoc_generator(day6)]
-fn parse_groups(input: &str) -> Vec<Group> {
-    input.split("\n\n").map(|g| g.lines().map(|s| s.to_string()).collect()).collect()
+fn parse_groups(input: &str) -> Result<Vec<Group>, ParseError> {
+    let src = Input::new(6, input);
+    input.split("\n\n").map(|g| g.lines().map(|s| {
+        match s.find(|c: char| !c.is_ascii_lowercase()) {
+            Some(i) => Err(src.error(&s[i..], "questions a to z")),
+            None => Ok(s.to_string()),
+        }
+    }).collect()).collect()
 }
 
 #[aoc(day6, part1)]
 pub fn solve_part1(input: &[Group]) -> usize {
-    input.iter().map(|g| count_any(g)).sum()
+    input.iter().map(count_any).sum()
 }
 
 #[aoc(day6, part2)]
 pub fn solve_part2(input: &[Group]) -> usize {
-    input.iter().map(|g| count_all(g)).sum()
+    input.iter().map(count_all).sum()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &'static str = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
+    const TEST_INPUT: &str = "abc\n\na\nb\nc\n\nab\nac\n\na\na\na\na\n\nb";
 
     #[test]
     fn test_count_any() {
@@ -50,11 +58,11 @@ mod tests {
 
     #[test]
     fn test_part1_solver() {
-        assert_eq!(solve_part1(&parse_groups(TEST_INPUT)), 11);
+        assert_eq!(solve_part1(&parse_groups(TEST_INPUT).unwrap()), 11);
     }
 
     #[test]
     fn test_part2_solver() {
-        assert_eq!(solve_part2(&parse_groups(TEST_INPUT)), 6);
+        assert_eq!(solve_part2(&parse_groups(TEST_INPUT).unwrap()), 6);
     }
 }
@@ -0,0 +1,175 @@
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt;
+
+use crate::parse::{Input, ParseError};
+
+
+const TARGET: &str = "shiny gold";
+
+
+/// Directed graph from each bag to the bags it directly contains, weighted by their number.
+pub type Rules = HashMap<String, Vec<(u64, String)>>;
+
+
+/// Bag rules that require a bag to contain itself.
+#[derive(Debug, PartialEq)]
+pub struct Cycle(Vec<String>);
+
+
+impl fmt::Display for Cycle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bags contain themselves: {}", self.0.join(" -> "))
+    }
+}
+
+
+impl Error for Cycle {}
+
+
+#[aoc_generator(day7)]
+fn parse_rules(input: &str) -> Result<Rules, ParseError> {
+    let src = Input::new(7, input);
+    let re = Regex::new(r"^(\d+) (\w+ \w+) bags?$").unwrap();
+    input.lines().map(|l| {
+        let mut parts = l.split(" bags contain ");
+        let outer = parts.next().unwrap().to_string();
+        let contents = src.expect(parts.next(), l, "\" bags contain \"")?;
+        let contents = contents.strip_suffix('.').ok_or_else(|| src.error(&contents[contents.len()..], "\".\""))?;
+        if contents == "no other bags" {
+            return Ok((outer, Vec::new()));
+        }
+        let inner = contents.split(", ").map(|item| {
+            let cap = re.captures(item).ok_or_else(|| src.error(item, "\"no other bags\" or a bag count like \"2 muted yellow bags\""))?;
+            Ok((src.parse(cap.get(1).unwrap().as_str(), "a number")?, cap[2].to_string()))
+        }).collect::<Result<_, ParseError>>()?;
+        Ok((outer, inner))
+    }).collect()
+}
+
+
+/// Inverts the edges of the graph, mapping each bag to the bags directly containing it.
+fn containers(rules: &Rules) -> HashMap<&str, Vec<&str>> {
+    let mut inv: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (outer, inner) in rules.iter() {
+        for (_, bag) in inner.iter() {
+            inv.entry(bag).or_default().push(outer);
+        }
+    }
+    inv
+}
+
+
+/// Returns all bags that eventually contain the given bag.
+fn all_containers<'a>(rules: &'a Rules, bag: &str) -> HashSet<&'a str> {
+    let inv = containers(rules);
+    let mut found = HashSet::new();
+    let mut todo = vec![bag];
+    while let Some(b) = todo.pop() {
+        for &c in inv.get(b).into_iter().flatten() {
+            if found.insert(c) {
+                todo.push(c);
+            }
+        }
+    }
+    found
+}
+
+
+/// Counts the bags inside the given bag, memoising results for each visited bag.
+/// The path from the outermost bag is kept to detect bags containing themselves.
+fn count_inside<'a>(rules: &'a Rules, bag: &'a str, memo: &mut HashMap<&'a str, u64>,
+                    path: &mut Vec<&'a str>) -> Result<u64, Cycle> {
+    if let Some(&n) = memo.get(bag) {
+        return Ok(n);
+    }
+    if let Some(i) = path.iter().position(|&b| b == bag) {
+        let mut cycle: Vec<String> = path[i..].iter().map(|s| s.to_string()).collect();
+        cycle.push(bag.to_string());
+        return Err(Cycle(cycle));
+    }
+    path.push(bag);
+    let mut n = 0;
+    for (k, inner) in rules.get(bag).into_iter().flatten() {
+        n += k * (1 + count_inside(rules, inner, memo, path)?);
+    }
+    path.pop();
+    memo.insert(bag, n);
+    Ok(n)
+}
+
+
+#[aoc(day7, part1)]
+pub fn solve_part1(input: &Rules) -> usize {
+    all_containers(input, TARGET).len()
+}
+
+
+#[aoc(day7, part2)]
+pub fn solve_part2(input: &Rules) -> Result<u64, Cycle> {
+    count_inside(input, TARGET, &mut HashMap::new(), &mut Vec::new())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = "light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.";
+
+    const TEST_INPUT2: &str = "shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.";
+
+    #[test]
+    fn test_parser() {
+        let rules = parse_rules(TEST_INPUT).unwrap();
+        assert_eq!(rules["light red"], vec![(1, "bright white".to_string()), (2, "muted yellow".to_string())]);
+        assert_eq!(rules["faded blue"], vec![]);
+    }
+
+    #[test]
+    fn test_parser_errors() {
+        let err = parse_rules("light red bags contain 1x bags.").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (24, "1x bags"));
+        let err = parse_rules("light red bags contain 1 bright white bag, 2 muted bags.").unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (44, "2 muted bags"));
+        let err = parse_rules("light red bags contain 1 bright white bag").unwrap_err();
+        assert_eq!((err.column, err.text.as_str(), err.expected.as_str()), (42, "", "\".\""));
+        assert!(parse_rules("light red bags contain no bags.").is_err());
+    }
+
+    #[test]
+    fn test_cycle() {
+        let rules = parse_rules("shiny gold bags contain 1 dark red bag.
+dark red bags contain 2 pale blue bags, 1 shiny gold bag.
+pale blue bags contain no other bags.").unwrap();
+        let err = solve_part2(&rules).unwrap_err();
+        assert_eq!(err, Cycle(vec!["shiny gold".to_string(), "dark red".to_string(), "shiny gold".to_string()]));
+        assert_eq!(err.to_string(), "bags contain themselves: shiny gold -> dark red -> shiny gold");
+    }
+
+    #[test]
+    fn test_part1_solver() {
+        assert_eq!(solve_part1(&parse_rules(TEST_INPUT).unwrap()), 4);
+    }
+
+    #[test]
+    fn test_part2_solver() {
+        assert_eq!(solve_part2(&parse_rules(TEST_INPUT).unwrap()), Ok(32));
+        assert_eq!(solve_part2(&parse_rules(TEST_INPUT2).unwrap()), Ok(126));
+    }
+}
@@ -2,6 +2,8 @@
 //use itertools::Itertools;
 use std::collections::HashSet;
 
+use crate::parse::{Input, ParseError};
+
 #[derive(Clone, Debug, PartialEq)]
 enum Op {
     Acc,
@@ -17,19 +19,19 @@ pub struct Instr {
 
 impl Instr {
     fn new(op: Op, arg: i32) -> Instr {
-        Instr { op: op, arg: arg }
+        Instr { op, arg }
     }
 
     fn acc(arg: i32) -> Instr {
-        Instr { op: Op::Acc, arg: arg }
+        Instr { op: Op::Acc, arg }
     }
 
     fn jmp(arg: i32) -> Instr {
-        Instr { op: Op::Jmp, arg: arg }
+        Instr { op: Op::Jmp, arg }
     }
 
     fn nop(arg: i32) -> Instr {
-        Instr { op: Op::Nop, arg: arg }
+        Instr { op: Op::Nop, arg }
     }
 }
 
@@ -40,8 +42,8 @@ struct Interp<'a> {
 }
 
 impl<'a> Interp<'a> {
-    fn new(code: &[Instr]) -> Interp {
-        Interp { pc: 0, acc: 0, code: code }
+    fn new(code: &[Instr]) -> Interp<'_> {
+        Interp { pc: 0, acc: 0, code }
     }
 
     /// Run the instruction at the current value of pc.
@@ -76,15 +78,18 @@ impl<'a> Interp<'a> {
 }
 
 #[aoc_generator(day8)]
-fn parse_instr(input: &str) -> Vec<Instr> {
+fn parse_instr(input: &str) -> Result<Vec<Instr>, ParseError> {
+    let src = Input::new(8, input);
     input.lines().map(|l| {
         let mut parts = l.split_whitespace();
-        Instr { op: match parts.next() {
-            Some("acc") => Op::Acc,
-            Some("jmp") => Op::Jmp,
-            Some("nop") => Op::Nop,
-            _ => panic!("unkown instruction"),
-        }, arg: parts.next().unwrap().parse().unwrap() }
+        let op = src.expect(parts.next(), l, "an instruction")?;
+        let arg = src.expect(parts.next(), l, "an argument")?;
+        Ok(Instr { op: match op {
+            "acc" => Op::Acc,
+            "jmp" => Op::Jmp,
+            "nop" => Op::Nop,
+            _ => return Err(src.error(op, "acc, jmp or nop")),
+        }, arg: src.parse(arg, "a signed number")? })
     }).collect()
 }
 
@@ -100,7 +105,7 @@ struct Mutations<'a> {
 }
 
 impl<'a> Mutations<'a> {
-    fn new(code: &[Instr]) -> Mutations {
+    fn new(code: &[Instr]) -> Mutations<'_> {
         Mutations{ original: code, cursor: 0 }
     }
 }
@@ -139,11 +144,11 @@ impl<'a> Iterator for Mutations<'a> {
 }
 
 trait Mutation {
-    fn mutations(&self) -> Mutations;
+    fn mutations(&self) -> Mutations<'_>;
 }
 
 impl Mutation for [Instr] {
-    fn mutations(&self) -> Mutations {
+    fn mutations(&self) -> Mutations<'_> {
         Mutations::new(self)
     }
 }
@@ -164,22 +169,32 @@ pub fn solve_part2(input: &[Instr]) -> i32 {
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &'static str = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6";
+    const TEST_INPUT: &str = "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6";
 
     #[test]
     fn test_parser() {
         let input = "nop +0\nacc +1";
         let expect = vec![Instr::new(Op::Nop, 0), Instr::new(Op::Acc, 1)];
-        assert_eq!(parse_instr(&input), expect);
+        assert_eq!(parse_instr(input).unwrap(), expect);
+    }
+
+    #[test]
+    fn test_parser_errors() {
+        let err = parse_instr("nop +0\nmov +1").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "mov"));
+        let err = parse_instr("nop +0\nacc").unwrap_err();
+        assert_eq!((err.line, err.column, err.expected.as_str()), (2, 4, "an argument"));
+        assert_eq!(parse_instr("jmp 1x").unwrap_err().to_string(),
+                   "day 8, line 1, column 5: expected a signed number, found \"1x\"\n  |\n1 | jmp 1x\n  |     ^^");
     }
 
     #[test]
     fn test_part1_solver() {
-        assert_eq!(solve_part1(&parse_instr(TEST_INPUT)), 5);
+        assert_eq!(solve_part1(&parse_instr(TEST_INPUT).unwrap()), 5);
     }
 
     #[test]
     fn test_part2_solver() {
-        assert_eq!(solve_part2(&parse_instr(TEST_INPUT)), 8);
+        assert_eq!(solve_part2(&parse_instr(TEST_INPUT).unwrap()), 8);
     }
 }
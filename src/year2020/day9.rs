@@ -1,16 +1,19 @@
 use itertools::iproduct;
 
+use crate::parse::{Input, ParseError};
+
 
 #[aoc_generator(day9)]
-fn parse_nums(input: &str) -> Vec<u64> {
-    input.lines().map(|l| l.parse().unwrap()).collect()
+fn parse_nums(input: &str) -> Result<Vec<u64>, ParseError> {
+    let src = Input::new(9, input);
+    input.lines().map(|l| src.parse(l, "a number")).collect()
 }
 
 fn validate_xmas(stream: &[u64], pre: usize) -> Option<u64> {
     for n in pre..stream.len() {
         let it1 = stream[n-pre..n].iter();
         let it2 = it1.clone();
-        if let None = iproduct!(it1, it2).filter(|(&i, &j)| i+j == stream[n]).next() {
+        if iproduct!(it1, it2).filter(|(&i, &j)| i+j == stream[n]).next().is_none() {
             return Some(stream[n]);
         }
     }
@@ -49,7 +52,7 @@ mod tests {
     #[test]
     fn test_parser() {
         let input = "0\n15";
-        assert_eq!(parse_nums(input), vec![0, 15]);
+        assert_eq!(parse_nums(input).unwrap(), vec![0, 15]);
     }
 
     #[test]
@@ -0,0 +1,94 @@
+//! Runs the solvers with a recorded answer in answers/2020.toml on their input and compares.
+//! Adding an answer needs a test below, recorded_answers_are_checked fails otherwise.
+
+extern crate aoc;
+
+use std::fs;
+use std::path::Path;
+
+use aoc::answers::Answers;
+use aoc::registry;
+
+
+const YEAR: u32 = 2020;
+
+
+fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+
+fn input(day: u32) -> Option<String> {
+    fs::read_to_string(root().join(format!("input/{}/day{}.txt", YEAR, day))).ok()
+}
+
+
+fn check(day: u32, part: u32) {
+    let answers = Answers::load(root(), YEAR).unwrap();
+    let expected = answers.get(day, part).unwrap_or_else(|| panic!("no recorded answer for day {} part {}", day, part));
+    let input = input(day).unwrap_or_else(|| panic!("no input for day {}", day));
+    let outcome = registry::find(YEAR, day, part).unwrap().run(&input).unwrap_or_else(|e| panic!("{}", e));
+    assert_eq!(outcome.answer, expected, "day {} part {}", day, part);
+}
+
+
+/// Generates a test for each listed part, and CHECKED with all of them.
+macro_rules! answer_tests {
+    ($($name:ident: $day:literal, $part:literal;)*) => {
+        const CHECKED: &[(u32, u32)] = &[$(($day, $part)),*];
+
+        $(
+            #[test]
+            fn $name() {
+                check($day, $part);
+            }
+        )*
+    };
+}
+
+
+// only parts with a recorded answer, the others have no input yet
+answer_tests! {
+    day1_part1: 1, 1;
+    day1_part2: 1, 2;
+    day2_part1: 2, 1;
+    day2_part2: 2, 2;
+    day3_part1: 3, 1;
+    day3_part2: 3, 2;
+    day5_part1: 5, 1;
+    day5_part2: 5, 2;
+    day6_part1: 6, 1;
+    day6_part2: 6, 2;
+    day8_part1: 8, 1;
+    day8_part2: 8, 2;
+    day9_part1: 9, 1;
+    day9_part2: 9, 2;
+    day10_part1: 10, 1;
+    day10_part2: 10, 2;
+    day11_part1: 11, 1;
+    day11_part2: 11, 2;
+    day12_part1: 12, 1;
+    day12_part2: 12, 2;
+    day13_part1: 13, 1;
+    day13_part2: 13, 2;
+    day14_part1: 14, 1;
+    day14_part2: 14, 2;
+    day15_part1: 15, 1;
+    day15_part2: 15, 2;
+    day17_part1: 17, 1;
+    day17_part2: 17, 2;
+    day18_part1: 18, 1;
+    day18_part2: 18, 2;
+    day19_part1: 19, 1;
+    day19_part2: 19, 2;
+}
+
+
+#[test]
+fn recorded_answers_are_checked() {
+    let answers = Answers::load(root(), YEAR).unwrap();
+    for (day, part, _) in answers.iter() {
+        assert!(CHECKED.contains(&(day, part)), "answer for day {} part {} is not checked", day, part);
+    }
+    assert_eq!(answers.iter().count(), CHECKED.len());
+}